@@ -0,0 +1,97 @@
+//! The answers file of a year records answers that are known to be correct, keyed by day and part:
+//!
+//! ```toml
+//! [day1]
+//! part1 = "1941353"
+//! part2 = "22539317"
+//! ```
+
+use crate::{files::read_if_exists, Answer, Part};
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    path::{Path, PathBuf},
+};
+
+/// The answers file of `year` that is checked into the repository, next to the solutions.
+pub fn default_path(year: u16) -> PathBuf {
+    Path::new(crate::scaffold::SRC_DIR)
+        .join(format!("y{year}"))
+        .join("answers.toml")
+}
+
+#[derive(Default)]
+pub struct Answers {
+    answers: BTreeMap<(u8, Part), String>,
+}
+
+impl Answers {
+    /// Reads the answers file at `path`. A missing file counts as having no answers recorded.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        match read_if_exists(path)? {
+            Some(text) => Self::parse(&text),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, Box<dyn Error>> {
+        let table: toml::Table = text.parse()?;
+        let mut answers = BTreeMap::new();
+        for (day_key, parts) in table {
+            let day = day_key
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .ok_or_else(|| format!("expected a key like `day1`, found `{day_key}`"))?;
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| format!("`{day_key}` must be a table"))?;
+            for (part_key, answer) in parts {
+                let part = match part_key.as_str() {
+                    "part1" => Part::One,
+                    "part2" => Part::Two,
+                    _ => {
+                        return Err(
+                            format!("expected `part1` or `part2`, found `{part_key}`").into()
+                        )
+                    }
+                };
+                let answer = match answer {
+                    toml::Value::String(answer) => answer.clone(),
+                    toml::Value::Integer(answer) => answer.to_string(),
+                    _ => {
+                        return Err(
+                            format!("answer for {day_key}.{part_key} must be a string").into()
+                        )
+                    }
+                };
+                answers.insert((day, part), answer);
+            }
+        }
+        Ok(Answers { answers })
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_toml())
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut table = toml::Table::new();
+        for (&(day, part), answer) in &self.answers {
+            let parts = table
+                .entry(format!("day{day}"))
+                .or_insert_with(|| toml::Table::new().into());
+            if let toml::Value::Table(parts) = parts {
+                parts.insert(format!("part{part}"), answer.clone().into());
+            }
+        }
+        table.to_string()
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn record(&mut self, day: u8, part: Part, answer: &Answer) {
+        self.answers.insert((day, part), answer.to_string());
+    }
+}
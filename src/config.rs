@@ -0,0 +1,105 @@
+//! Settings for talking to the puzzle server, read from `aoc.toml`:
+//!
+//! ```toml
+//! session = "53616c7465645f5f..."
+//! base_url = "https://adventofcode.com"
+//! data_dir = "data"
+//! ```
+//!
+//! Every setting is optional and can be overridden with the environment variables `AOC_SESSION`,
+//! `AOC_BASE_URL` and `AOC_DATA_DIR`. The session token is the value of the `session` cookie of a
+//! logged in browser.
+
+use crate::files::read_if_exists;
+use serde::Deserialize;
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+};
+
+/// The config file next to `Cargo.toml`. It holds a secret, so it is ignored by git.
+pub const DEFAULT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/aoc.toml");
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_DATA_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/data");
+
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+    pub data_dir: Option<PathBuf>,
+}
+
+impl Config {
+    /// Reads the config file at `path` and applies the overrides from the environment. A missing
+    /// file counts as an empty config. A relative `data_dir` is relative to the config file.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let mut config = match read_if_exists(path)? {
+            Some(text) => Self::parse(&text)?,
+            None => Self::default(),
+        };
+        if let (Some(data_dir), Some(config_dir)) = (&mut config.data_dir, path.parent()) {
+            *data_dir = config_dir.join(&*data_dir);
+        }
+
+        if let Ok(session) = std::env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = std::env::var("AOC_BASE_URL") {
+            config.base_url = Some(base_url);
+        }
+        if let Some(data_dir) = std::env::var_os("AOC_DATA_DIR") {
+            config.data_dir = Some(data_dir.into());
+        }
+        Ok(config)
+    }
+
+    pub fn parse(text: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(toml::from_str(text)?)
+    }
+
+    pub fn session(&self) -> Result<&str, Box<dyn Error>> {
+        match self.session.as_deref().map(str::trim) {
+            Some(session) if !session.is_empty() => Ok(session),
+            _ => Err(format!(
+                "no session token, set `session` in {DEFAULT_PATH} or the AOC_SESSION variable"
+            )
+            .into()),
+        }
+    }
+
+    /// The server URL without a trailing slash.
+    pub fn base_url(&self) -> &str {
+        self.base_url
+            .as_deref()
+            .unwrap_or(DEFAULT_BASE_URL)
+            .trim_end_matches('/')
+    }
+
+    pub fn data_dir(&self) -> &Path {
+        self.data_dir
+            .as_deref()
+            .unwrap_or(Path::new(DEFAULT_DATA_DIR))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_config() {
+        let config = Config::parse(
+            "session = \"abc\"\nbase_url = \"http://localhost:8080/\"\ndata_dir = \"/tmp/aoc\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.session().unwrap(), "abc");
+        assert_eq!(config.base_url(), "http://localhost:8080");
+        assert_eq!(config.data_dir(), Path::new("/tmp/aoc"));
+
+        let config = Config::parse("").unwrap();
+        assert!(config.session().is_err());
+        assert_eq!(config.base_url(), DEFAULT_BASE_URL);
+        assert!(Config::parse("token = \"abc\"").is_err());
+    }
+}
@@ -0,0 +1,270 @@
+//! Detecting when a deterministic simulation starts repeating itself.
+//!
+//! A simulation is given by its initial state and a `step` function computing the next state,
+//! or `None` once the simulation has ended. Since `step` only sees the state, the states after
+//! the first repeated one must run in a loop forever.
+
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    hash::Hash,
+};
+
+/// Where the states of a simulation start repeating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Loop {
+    /// The number of steps until the first state that is repeated later on
+    pub start: usize,
+    /// The number of steps after which the states repeat
+    pub length: usize,
+}
+
+impl Loop {
+    /// The smallest number of steps that leads to the same state as `n_steps` steps.
+    pub fn reduce(self, n_steps: usize) -> usize {
+        if n_steps < self.start {
+            n_steps
+        } else {
+            self.start + (n_steps - self.start) % self.length
+        }
+    }
+}
+
+/// Finds the loop by remembering every state. Takes the fewest steps, but needs the states to be
+/// hashable and keeps all of them in memory.
+pub fn find_loop<S: Clone + Eq + Hash>(
+    initial: S,
+    step: impl FnMut(&S) -> Option<S>,
+) -> Option<Loop> {
+    match simulate(initial, step, None) {
+        (_, Ended::Loop(found)) => Some(found),
+        _ => None,
+    }
+}
+
+/// Finds the loop with Floyd's tortoise and hare, using constant memory.
+pub fn find_loop_floyd<S: Clone + PartialEq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Option<Loop> {
+    // the hare runs twice as fast, so they meet once both are in the loop
+    let mut tortoise = step(&initial)?;
+    let mut hare = step(&tortoise)?;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        let halfway = step(&hare)?;
+        hare = step(&halfway)?;
+    }
+
+    // The hare is now a multiple of the loop length ahead, so restarting the tortoise, they
+    // first meet at the start of the loop.
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise)?;
+    while tortoise != hare {
+        hare = step(&hare)?;
+        length += 1;
+    }
+    Some(Loop { start, length })
+}
+
+/// Finds the loop with Brent's algorithm, using constant memory and fewer steps than
+/// [`find_loop_floyd`].
+pub fn find_loop_brent<S: Clone + PartialEq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Option<Loop> {
+    // The tortoise teleports to the hare whenever the hare has taken a power of two steps since
+    // the last time, so they meet once the hare completes the loop within such a stretch.
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial)?;
+    let mut stretch = 1;
+    let mut length = 1;
+    while tortoise != hare {
+        if length == stretch {
+            tortoise = hare.clone();
+            stretch *= 2;
+            length = 0;
+        }
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    // with the hare one loop length ahead, they first meet at the start of the loop
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare)?;
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+    Some(Loop { start, length })
+}
+
+/// The states a simulation has been in, see [`first_repeated`].
+pub trait StateSet<S> {
+    /// Adds `state`, returning whether it wasn't in the set before.
+    fn insert(&mut self, state: &S) -> bool;
+}
+
+impl<S: Clone + Eq + Hash> StateSet<S> for HashSet<S> {
+    fn insert(&mut self, state: &S) -> bool {
+        HashSet::insert(self, state.clone())
+    }
+}
+
+/// The first state that is reached a second time, or `None` if the simulation ends before. Like
+/// [`find_loop`], but only tells whether there is a loop, so `seen` can be any set of states, for
+/// example a bitset specialized to them. Afterwards, `seen` holds every state visited.
+pub fn first_repeated<S>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+    seen: &mut impl StateSet<S>,
+) -> Option<S> {
+    let mut state = initial;
+    while seen.insert(&state) {
+        state = step(&state)?;
+    }
+    Some(state)
+}
+
+/// The state after `n_steps` steps, or `None` if the simulation ends before. Once a loop is
+/// found, the remaining steps are skipped, so `n_steps` can be arbitrarily large.
+pub fn nth_state<S: Clone + Eq + Hash>(
+    initial: S,
+    step: impl FnMut(&S) -> Option<S>,
+    n_steps: usize,
+) -> Option<S> {
+    let (mut states, ended) = simulate(initial, step, Some(n_steps));
+    match ended {
+        Ended::Loop(found) => Some(states.swap_remove(found.reduce(n_steps))),
+        Ended::Limit => states.pop(),
+        Ended::End => None,
+    }
+}
+
+enum Ended {
+    Loop(Loop),
+    /// The state after the maximum number of steps was reached
+    Limit,
+    /// `step` returned `None`
+    End,
+}
+
+/// Runs the simulation until it loops, ends, or reaches `max_steps`. Returns all states seen
+/// until then, in order.
+fn simulate<S: Clone + Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+    max_steps: Option<usize>,
+) -> (Vec<S>, Ended) {
+    let mut steps_until = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+    loop {
+        let n_steps = states.len();
+        match steps_until.entry(state.clone()) {
+            Entry::Occupied(entry) => {
+                let start = *entry.get();
+                let length = n_steps - start;
+                return (states, Ended::Loop(Loop { start, length }));
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(n_steps);
+            }
+        }
+        if max_steps == Some(n_steps) {
+            states.push(state);
+            return (states, Ended::Limit);
+        }
+        let Some(next_state) = step(&state) else {
+            return (states, Ended::End);
+        };
+        states.push(state);
+        state = next_state;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A sequence running into the loop 17 → 18 → ... → 30 → 17 after 17 steps
+    fn rho(state: &u32) -> Option<u32> {
+        Some(if *state == 30 { 17 } else { state + 1 })
+    }
+
+    #[test]
+    fn all_variants_find_the_same_loop() {
+        let expected = Some(Loop {
+            start: 17,
+            length: 14,
+        });
+        assert_eq!(find_loop(0, rho), expected);
+        assert_eq!(find_loop_floyd(0, rho), expected);
+        assert_eq!(find_loop_brent(0, rho), expected);
+
+        for initial in 0..1000 {
+            let step = |&x: &u64| Some((x * x + 1) % 1009);
+            let found = find_loop(initial, step);
+            assert!(found.is_some());
+            assert_eq!(find_loop_floyd(initial, step), found, "{initial}");
+            assert_eq!(find_loop_brent(initial, step), found, "{initial}");
+        }
+    }
+
+    #[test]
+    fn immediate_and_missing_loops() {
+        let fixed_point = Some(Loop {
+            start: 0,
+            length: 1,
+        });
+        assert_eq!(find_loop(5, |&x| Some(x)), fixed_point);
+        assert_eq!(find_loop_floyd(5, |&x| Some(x)), fixed_point);
+        assert_eq!(find_loop_brent(5, |&x| Some(x)), fixed_point);
+
+        let countdown = |&x: &u32| x.checked_sub(1);
+        assert_eq!(find_loop(10, countdown), None);
+        assert_eq!(find_loop_floyd(10, countdown), None);
+        assert_eq!(find_loop_brent(10, countdown), None);
+    }
+
+    #[test]
+    fn first_repeated_state() {
+        let mut seen = HashSet::new();
+        assert_eq!(first_repeated(0, rho, &mut seen), Some(17));
+        assert_eq!(seen.len(), 31);
+
+        let countdown = |&x: &u32| x.checked_sub(1);
+        let mut seen = HashSet::new();
+        assert_eq!(first_repeated(10, countdown, &mut seen), None);
+        assert_eq!(seen.len(), 11);
+    }
+
+    #[test]
+    fn fast_forward() {
+        for n_steps in 0..100 {
+            let naive = (0..n_steps).fold(0, |state, _| rho(&state).unwrap());
+            assert_eq!(nth_state(0, rho, n_steps), Some(naive), "{n_steps}");
+        }
+        let far_ahead = 1_000_000_000_000_000;
+        assert_eq!(
+            nth_state(0, rho, far_ahead),
+            Some((17 + (far_ahead - 17) % 14) as u32)
+        );
+
+        let countdown = |&x: &u32| x.checked_sub(1);
+        assert_eq!(nth_state(10, countdown, 10), Some(0));
+        assert_eq!(nth_state(10, countdown, 11), None);
+    }
+}
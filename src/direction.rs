@@ -0,0 +1,151 @@
+//! The 8 compass directions on a grid where `y` grows downwards.
+
+use nalgebra::Vector2;
+
+/// Ordered clockwise, starting at north.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::NorthEast,
+        Direction::SouthEast,
+        Direction::SouthWest,
+        Direction::NorthWest,
+    ];
+
+    /// A number in `0..8` unique to each direction, for use in arrays and bitsets.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// A single bit unique to each direction, so that sets of directions fit into a `u8`.
+    pub fn bit(self) -> u8 {
+        1 << self.index()
+    }
+
+    fn rotate_clockwise(self, n_eighths: usize) -> Self {
+        Self::ALL[(self.index() + n_eighths) % 8]
+    }
+
+    /// Rotates by 90° clockwise.
+    pub fn turn_right(self) -> Self {
+        self.rotate_clockwise(2)
+    }
+
+    /// Rotates by 90° counterclockwise.
+    pub fn turn_left(self) -> Self {
+        self.rotate_clockwise(6)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.rotate_clockwise(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        !self.index().is_multiple_of(2)
+    }
+
+    /// Parses the arrows `^`, `>`, `v` and `<`.
+    pub fn from_arrow(ch: char) -> Option<Self> {
+        match ch {
+            '^' => Some(Direction::North),
+            '>' => Some(Direction::East),
+            'v' => Some(Direction::South),
+            '<' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    /// The step from one cell to the next cell in this direction.
+    pub fn offset(self) -> (i8, i8) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::NorthEast => (1, -1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (0, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, -1),
+        }
+    }
+}
+
+impl From<Direction> for Vector2<i32> {
+    fn from(direction: Direction) -> Self {
+        let (dx, dy) = direction.offset();
+        Vector2::new(dx.into(), dy.into())
+    }
+}
+
+impl From<Direction> for Vector2<i64> {
+    fn from(direction: Direction) -> Self {
+        let (dx, dy) = direction.offset();
+        Vector2::new(dx.into(), dy.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotations() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.reverse());
+            assert_eq!(direction.reverse().reverse(), direction);
+        }
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::NorthWest.turn_left(), Direction::SouthWest);
+    }
+
+    #[test]
+    fn turning_right_matches_rotation_matrix() {
+        let rotate_right_90_deg = nalgebra::Matrix2::new(0, -1, 1, 0);
+        for direction in Direction::ALL {
+            assert_eq!(
+                Vector2::<i32>::from(direction.turn_right()),
+                rotate_right_90_deg * Vector2::<i32>::from(direction)
+            );
+        }
+    }
+
+    #[test]
+    fn parse_arrows() {
+        let directions = "^>v<"
+            .chars()
+            .map(Direction::from_arrow)
+            .collect::<Vec<_>>();
+        assert_eq!(directions, Direction::ORTHOGONAL.map(Some));
+        assert_eq!(Direction::from_arrow('x'), None);
+    }
+}
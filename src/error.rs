@@ -0,0 +1,91 @@
+use std::fmt;
+
+/// Puzzle input that doesn't have the format a solver expects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Filled in by the runner, solvers don't know which day they are.
+    pub day: Option<u8>,
+    /// 1-based line of the offending text
+    pub line: usize,
+    /// 1-based column of the offending text, counted in chars
+    pub column: usize,
+    pub text: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Creates an error pointing at `text`, which must be a slice of `input`.
+    pub fn new(input: &str, text: &str, expected: impl Into<String>) -> Self {
+        let offset = (text.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset + text.len() <= input.len())
+            .expect("offending text must be a slice of the input");
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+
+        ParseError {
+            day: None,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: text.to_owned(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Creates an error for input that ended while `expected` was still missing.
+    pub fn missing(input: &str, expected: impl Into<String>) -> Self {
+        Self::new(input, &input[input.len()..], expected)
+    }
+
+    pub fn in_day(self, day: u8) -> Self {
+        ParseError {
+            day: Some(day),
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {day}, ")?;
+        }
+        write!(
+            f,
+            "line {}, column {}: expected {}, ",
+            self.line, self.column, self.expected
+        )?;
+        match self.text.as_str() {
+            "" => write!(f, "found nothing"),
+            text => write!(f, "found `{}`", text.escape_debug()),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position_of_offending_text() {
+        let input = "12 34\nab öx\n";
+        let err = ParseError::new(input, &input[11..12], "a digit");
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(
+            err.in_day(3).to_string(),
+            "day 3, line 2, column 5: expected a digit, found `x`"
+        );
+    }
+
+    #[test]
+    fn missing_text_points_at_end_of_input() {
+        let err = ParseError::missing("abc", "a guard");
+        assert_eq!((err.line, err.column), (1, 4));
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 4: expected a guard, found nothing"
+        );
+    }
+}
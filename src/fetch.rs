@@ -0,0 +1,80 @@
+//! Downloading puzzle inputs into the data directory. Each input is requested from the server only
+//! once, later runs read the cached file.
+
+use crate::{config::Config, http};
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+};
+
+/// Where `fetch_input` stores the input of `day` of `year`.
+pub fn cached_input_path(data_dir: &Path, year: u16, day: u8) -> PathBuf {
+    data_dir
+        .join("inputs")
+        .join(year.to_string())
+        .join(format!("day{day}.txt"))
+}
+
+/// Downloads the input of `day` of `year` and returns the path of the cached file. Refuses to
+/// download an input that is already cached.
+pub fn fetch_input(config: &Config, year: u16, day: u8) -> Result<PathBuf, Box<dyn Error>> {
+    let path = cached_input_path(config.data_dir(), year, day);
+    if path.exists() {
+        return Err(format!(
+            "{} already exists, delete it to fetch again",
+            path.display()
+        )
+        .into());
+    }
+
+    let url = format!("{}/{year}/day/{day}/input", config.base_url());
+    let input = http::get(&url, config.session()?)?;
+    if input.is_empty() {
+        return Err(format!("{url} returned an empty input").into());
+    }
+
+    // write to a temporary file first, so that an interrupted download isn't mistaken for a cached
+    // input
+    std::fs::create_dir_all(
+        path.parent()
+            .expect("cached inputs are inside the data directory"),
+    )?;
+    let partial_path = path.with_extension("txt.part");
+    std::fs::write(&partial_path, input)?;
+    std::fs::rename(&partial_path, &path)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::test_server::{serve_once, test_config};
+
+    #[test]
+    fn fetches_and_caches_input() {
+        let (base_url, server) = serve_once("200 OK", "1 2\n3 4\n");
+        let config = test_config(base_url, "fetch");
+
+        let path = fetch_input(&config, 2023, 3).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "1 2\n3 4\n");
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2023/day/3/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=secret\r\n"));
+
+        // the server is gone, so this only succeeds if nothing is requested
+        let err = fetch_input(&config, 2023, 3).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+        std::fs::remove_dir_all(config.data_dir()).unwrap();
+    }
+
+    #[test]
+    fn reports_server_errors() {
+        let (base_url, server) = serve_once("400 Bad Request", "Please log in.\n");
+        let config = test_config(base_url, "fetch-error");
+
+        let err = fetch_input(&config, 2024, 4).unwrap_err();
+        assert!(err.to_string().ends_with("status 400: Please log in."));
+        server.join().unwrap();
+        assert!(!cached_input_path(config.data_dir(), 2024, 4).exists());
+    }
+}
@@ -0,0 +1,25 @@
+//! Reading the files the crate keeps its state in, which may not have been created yet.
+
+use std::{io, path::Path};
+
+/// The contents of the file at `path`, or `None` if there is no such file.
+pub fn read_if_exists(path: &Path) -> io::Result<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_file() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("missing-{}.toml", std::process::id()));
+        assert_eq!(read_if_exists(&path).unwrap(), None);
+        assert!(read_if_exists(&dir).is_err());
+    }
+}
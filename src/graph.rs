@@ -0,0 +1,553 @@
+//! Searches and orderings on graphs that are given implicitly, by a closure returning the
+//! neighbors (or successors) of a node. Nodes can be anything hashable: grid positions, puzzle
+//! states, page numbers, ...
+//!
+//! None of the algorithms recurse, so they work on arbitrarily deep graphs.
+
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Visits every node reachable from `starts` in breadth-first order, yielding each node once
+/// together with its distance in edges from the closest start.
+pub fn bfs<N, I, F>(starts: impl IntoIterator<Item = N>, neighbors: F) -> Bfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+{
+    let mut seen = HashSet::new();
+    let queue = starts
+        .into_iter()
+        .filter(|start| seen.insert(start.clone()))
+        .map(|start| (start, 0))
+        .collect();
+    Bfs {
+        queue,
+        seen,
+        neighbors,
+    }
+}
+
+pub struct Bfs<N, F> {
+    queue: VecDeque<(N, usize)>,
+    seen: HashSet<N>,
+    neighbors: F,
+}
+
+impl<N, I, F> Iterator for Bfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+{
+    type Item = (N, usize);
+
+    fn next(&mut self) -> Option<(N, usize)> {
+        let (node, distance) = self.queue.pop_front()?;
+        for neighbor in (self.neighbors)(&node) {
+            if self.seen.insert(neighbor.clone()) {
+                self.queue.push_back((neighbor, distance + 1));
+            }
+        }
+        Some((node, distance))
+    }
+}
+
+/// Visits every node reachable from `start` in depth-first preorder, yielding each node once.
+/// Neighbors are explored in the order `neighbors` returns them.
+pub fn dfs<N, I, F>(start: N, neighbors: F) -> Dfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+{
+    Dfs {
+        stack: vec![start],
+        seen: HashSet::new(),
+        neighbors,
+    }
+}
+
+pub struct Dfs<N, F> {
+    stack: Vec<N>,
+    seen: HashSet<N>,
+    neighbors: F,
+}
+
+impl<N, I, F> Iterator for Dfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+{
+    type Item = N;
+
+    fn next(&mut self) -> Option<N> {
+        let node = loop {
+            let node = self.stack.pop()?;
+            if self.seen.insert(node.clone()) {
+                break node;
+            }
+        };
+        // pushed in reverse, so that the first neighbor is popped first
+        let n_pending = self.stack.len();
+        let seen = &self.seen;
+        self.stack.extend(
+            (self.neighbors)(&node)
+                .into_iter()
+                .filter(|neighbor| !seen.contains(neighbor)),
+        );
+        self.stack[n_pending..].reverse();
+        Some(node)
+    }
+}
+
+/// Nodes forming a cycle: each has an edge to the next one, and the last one to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<N>(pub Vec<N>);
+
+/// Orders `nodes` so that every node comes before its successors (Kahn's algorithm). Nodes
+/// without ordering constraints keep their relative order. Edges to nodes that aren't part of
+/// `nodes` are ignored, so `successors` can come from a larger set of rules.
+///
+/// Fails with one of the cycles if there is no such order.
+pub fn topological_sort<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> Result<Vec<N>, Cycle<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut ids = HashMap::new();
+    let mut nodes_by_id = vec![];
+    for node in nodes {
+        if let Entry::Vacant(entry) = ids.entry(node.clone()) {
+            entry.insert(nodes_by_id.len());
+            nodes_by_id.push(node);
+        }
+    }
+
+    let edges = nodes_by_id
+        .iter()
+        .map(|node| {
+            successors(node)
+                .into_iter()
+                .filter_map(|successor| ids.get(&successor).copied())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let mut n_predecessors = vec![0; nodes_by_id.len()];
+    for &successor in edges.iter().flatten() {
+        n_predecessors[successor] += 1;
+    }
+
+    let mut ready = (0..nodes_by_id.len())
+        .filter(|&id| n_predecessors[id] == 0)
+        .collect::<VecDeque<_>>();
+    let mut order = Vec::with_capacity(nodes_by_id.len());
+    while let Some(id) = ready.pop_front() {
+        order.push(id);
+        for &successor in &edges[id] {
+            n_predecessors[successor] -= 1;
+            if n_predecessors[successor] == 0 {
+                ready.push_back(successor);
+            }
+        }
+    }
+
+    if order.len() == nodes_by_id.len() {
+        return Ok(order
+            .into_iter()
+            .map(|id| nodes_by_id[id].clone())
+            .collect());
+    }
+
+    // Every node left over still has a predecessor that is left over, so walking backwards from
+    // any of them must eventually run in a circle.
+    let mut predecessor = vec![None; nodes_by_id.len()];
+    for (id, successors) in edges.iter().enumerate() {
+        for &successor in successors {
+            if n_predecessors[id] > 0 {
+                predecessor[successor] = Some(id);
+            }
+        }
+    }
+    let mut walked = vec![];
+    let mut position_in_walk = HashMap::new();
+    let mut id = (0..nodes_by_id.len())
+        .find(|&id| n_predecessors[id] > 0)
+        .expect("unordered nodes are left over");
+    while let Entry::Vacant(entry) = position_in_walk.entry(id) {
+        entry.insert(walked.len());
+        walked.push(id);
+        id = predecessor[id].expect("left over nodes have a left over predecessor");
+    }
+    let cycle = walked[position_in_walk[&id]..]
+        .iter()
+        .rev()
+        .map(|&id| nodes_by_id[id].clone())
+        .collect();
+    Err(Cycle(cycle))
+}
+
+/// The cheapest path from `start` to a node satisfying `is_goal` (A* search), as its total cost
+/// and the nodes along it, including both ends. `successors` returns the nodes one step away
+/// together with the cost of that step.
+///
+/// `heuristic` must never overestimate the remaining cost to a goal, otherwise the path found
+/// may not be the cheapest.
+pub fn astar<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(C, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (nodes, goal_id) = cheapest_paths(start, successors, heuristic, is_goal);
+    let goal_id = goal_id?;
+    let mut path = vec![];
+    let mut next_id = Some(goal_id);
+    while let Some(id) = next_id {
+        path.push(nodes[id].node.clone());
+        next_id = nodes[id].reached_from;
+    }
+    path.reverse();
+    Some((nodes[goal_id].cost, path))
+}
+
+/// The cheapest path from `start` to a node satisfying `is_goal` (Dijkstra's algorithm), like
+/// [`astar`] without a heuristic.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(C, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// The cost of the cheapest path from `start` to every node reachable from it.
+pub fn dijkstra_all<N, C, I>(start: N, successors: impl FnMut(&N) -> I) -> HashMap<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (nodes, _) = cheapest_paths(start, successors, |_| C::default(), |_| false);
+    nodes
+        .into_iter()
+        .map(|reached| (reached.node, reached.cost))
+        .collect()
+}
+
+struct Reached<N, C> {
+    node: N,
+    /// The id of the previous node on the cheapest path found so far
+    reached_from: Option<usize>,
+    cost: C,
+}
+
+/// Searches cheapest paths until a goal is taken from the queue, or everything reachable has
+/// been explored. Returns all nodes reached, identified by their index, and the goal's id.
+fn cheapest_paths<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> (Vec<Reached<N, C>>, Option<usize>)
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    let mut ids = HashMap::from([(start.clone(), 0)]);
+    let mut nodes = vec![Reached {
+        node: start,
+        reached_from: None,
+        cost: C::default(),
+    }];
+
+    while let Some(Reverse((_, cost, id))) = queue.pop() {
+        if cost > nodes[id].cost {
+            // a cheaper way to this node has been found after queueing it
+            continue;
+        }
+        let node = nodes[id].node.clone();
+        if is_goal(&node) {
+            return (nodes, Some(id));
+        }
+
+        for (successor, step_cost) in successors(&node) {
+            let successor_cost = cost + step_cost;
+            let successor_id = match ids.entry(successor) {
+                Entry::Vacant(entry) => {
+                    nodes.push(Reached {
+                        node: entry.key().clone(),
+                        reached_from: Some(id),
+                        cost: successor_cost,
+                    });
+                    *entry.insert(nodes.len() - 1)
+                }
+                Entry::Occupied(entry) => {
+                    let successor_id = *entry.get();
+                    let reached = &mut nodes[successor_id];
+                    if successor_cost >= reached.cost {
+                        continue;
+                    }
+                    reached.reached_from = Some(id);
+                    reached.cost = successor_cost;
+                    successor_id
+                }
+            };
+            let estimate = successor_cost + heuristic(&nodes[successor_id].node);
+            queue.push(Reverse((estimate, successor_cost, successor_id)));
+        }
+    }
+    (nodes, None)
+}
+
+/// The number of distinct paths from `start` to nodes satisfying `is_goal`. A path may pass
+/// through goals on its way to another goal. Fails if a cycle is reachable from `start`, since
+/// there would be infinitely many paths.
+pub fn count_paths<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Result<u64, Cycle<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let reachable = dfs(start.clone(), &mut successors).collect::<Vec<_>>();
+    let order = topological_sort(reachable, &mut successors)?;
+
+    // going backwards, all successors of a node are counted before the node itself
+    let mut n_paths = HashMap::new();
+    for node in order.into_iter().rev() {
+        let n_paths_through_successors = successors(&node)
+            .into_iter()
+            .map(|successor| n_paths[&successor])
+            .sum::<u64>();
+        let n_paths_from_node = is_goal(&node) as u64 + n_paths_through_successors;
+        n_paths.insert(node, n_paths_from_node);
+    }
+    Ok(n_paths[&start])
+}
+
+/// Groups `nodes` into sets that are connected to each other, where `neighbors` must be
+/// symmetric. Nodes reachable from `nodes` are included as well. The components are ordered by
+/// their first node in `nodes`, and each one starts with that node.
+pub fn connected_components<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+) -> Vec<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut components = vec![];
+    for node in nodes {
+        if !seen.insert(node.clone()) {
+            continue;
+        }
+        let mut component = vec![node];
+        let mut next_idx = 0;
+        while let Some(node) = component.get(next_idx) {
+            let new_neighbors = neighbors(node)
+                .into_iter()
+                .filter(|neighbor| seen.insert(neighbor.clone()))
+                .collect::<Vec<_>>();
+            component.extend(new_neighbors);
+            next_idx += 1;
+        }
+        components.push(component);
+    }
+    components
+}
+
+/// Groups `nodes` and everything reachable from them into sets of nodes that can all reach each
+/// other (Tarjan's algorithm). The components come in reverse topological order: edges only
+/// lead from a component to the ones before it.
+pub fn strongly_connected_components<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> Vec<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    // nodes are identified by the order in which they are discovered
+    let mut ids = HashMap::new();
+    let mut nodes_by_id = vec![];
+    // the smallest id reachable from a node while it is being explored
+    let mut lowest_reachable = vec![];
+    let mut on_stack = vec![];
+    let mut stack = vec![];
+    let mut components = vec![];
+
+    for root in nodes {
+        if ids.contains_key(&root) {
+            continue;
+        }
+        // replaces the call stack of the recursive formulation
+        let mut exploring = vec![];
+        let mut undiscovered = Some(root);
+        loop {
+            if let Some(node) = undiscovered.take() {
+                let id = nodes_by_id.len();
+                ids.insert(node.clone(), id);
+                lowest_reachable.push(id);
+                on_stack.push(true);
+                stack.push(id);
+                exploring.push((id, successors(&node).into_iter()));
+                nodes_by_id.push(node);
+            }
+            let Some((id, node_successors)) = exploring.last_mut() else {
+                break;
+            };
+            let id = *id;
+
+            if let Some(successor) = node_successors.next() {
+                match ids.get(&successor) {
+                    None => undiscovered = Some(successor),
+                    Some(&successor_id) if on_stack[successor_id] => {
+                        lowest_reachable[id] = lowest_reachable[id].min(successor_id);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            exploring.pop();
+            if let Some(&(parent, _)) = exploring.last() {
+                lowest_reachable[parent] = lowest_reachable[parent].min(lowest_reachable[id]);
+            }
+            if lowest_reachable[id] == id {
+                let root_idx = stack.iter().rposition(|&member| member == id).unwrap();
+                let component = stack
+                    .drain(root_idx..)
+                    .map(|member| {
+                        on_stack[member] = false;
+                        nodes_by_id[member].clone()
+                    })
+                    .collect();
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Connectivity, Grid};
+
+    /// 0 → 1 → 2 → 3 → 1, 0 → 4 → 5, 6 alone
+    fn successors(node: &u8) -> Vec<u8> {
+        match node {
+            0 => vec![1, 4],
+            1 => vec![2],
+            2 => vec![3],
+            3 => vec![1],
+            4 => vec![5],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn breadth_and_depth_first_order() {
+        let visited = bfs([0], successors).collect::<Vec<_>>();
+        assert_eq!(visited, [(0, 0), (1, 1), (4, 1), (2, 2), (5, 2), (3, 3)]);
+        assert_eq!(dfs(0, successors).collect::<Vec<_>>(), [0, 1, 2, 3, 4, 5]);
+
+        // deep enough to overflow the stack if it recursed
+        let n_visited = dfs(0u32, |&n| (n < 1_000_000).then_some(n + 1)).count();
+        assert_eq!(n_visited, 1_000_001);
+    }
+
+    #[test]
+    fn topological_order_and_cycles() {
+        let order = topological_sort([5, 4, 0, 6], successors);
+        assert_eq!(order, Ok(vec![0, 6, 4, 5]));
+
+        let Err(Cycle(cycle)) = topological_sort(0..7, successors) else {
+            panic!("the cycle 1 → 2 → 3 → 1 wasn't found");
+        };
+        assert_eq!(cycle.len(), 3);
+        for (idx, node) in cycle.iter().enumerate() {
+            assert!(successors(node).contains(&cycle[(idx + 1) % cycle.len()]));
+        }
+
+        assert_eq!(count_paths(4, successors, |&n| n == 5), Ok(1));
+        assert!(count_paths(0, successors, |&n| n == 5).is_err());
+        let diamond = |&n: &u8| if n < 3 { vec![n + 1, n + 2] } else { vec![] };
+        assert_eq!(count_paths(0, diamond, |&n| n >= 3), Ok(5));
+    }
+
+    #[test]
+    fn shortest_paths_on_a_grid() {
+        let grid = Grid::parse("..#.\n.#..\n...#\n#...\n", |ch| ch == '#').unwrap();
+        let free_neighbors = |&pos: &(usize, usize)| {
+            grid.neighbors(pos, Connectivity::Orthogonal)
+                .filter(|&(neighbor, _)| !grid[neighbor])
+                .map(|(neighbor, _)| (neighbor, 1u32))
+                .collect::<Vec<_>>()
+        };
+        let goal = (3, 3);
+
+        let (cost, path) = dijkstra((0, 0), free_neighbors, |&pos| pos == goal).unwrap();
+        assert_eq!(cost, 6);
+        assert_eq!(path.len(), 7);
+        assert_eq!((path[0], path[6]), ((0, 0), goal));
+
+        let manhattan = |&(x, y): &(usize, usize)| (goal.0.abs_diff(x) + goal.1.abs_diff(y)) as u32;
+        let found = astar((0, 0), free_neighbors, manhattan, |&pos| pos == goal);
+        assert_eq!(found.map(|(cost, _)| cost), Some(6));
+        // walls can't be entered
+        assert_eq!(dijkstra((0, 0), free_neighbors, |&pos| pos == (2, 0)), None);
+
+        let costs = dijkstra_all((0, 0), free_neighbors);
+        assert_eq!(costs.len(), 12);
+        assert_eq!(costs[&(3, 1)], 6);
+    }
+
+    #[test]
+    fn components() {
+        let undirected = |node: &u8| match node {
+            0 | 2 => vec![1],
+            1 => vec![0, 2],
+            3 => vec![4],
+            4 => vec![3],
+            _ => vec![],
+        };
+        assert_eq!(
+            connected_components([2, 3, 5, 0], undirected),
+            [vec![2, 1, 0], vec![3, 4], vec![5]]
+        );
+
+        let mut components = strongly_connected_components(0..7, successors);
+        for component in &mut components {
+            component.sort();
+        }
+        assert_eq!(
+            components,
+            [vec![1, 2, 3], vec![5], vec![4], vec![0], vec![6]]
+        );
+    }
+}
@@ -0,0 +1,412 @@
+//! A rectangular grid of cells, as used by all the puzzles with a character map as input.
+//!
+//! Positions are `(x, y)` with `x` counting columns from the left and `y` counting rows from the
+//! top.
+
+use crate::{direction::Direction, error::ParseError, parse::lines};
+use nalgebra::Vector2;
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// Types that can be used to address a cell of a [`Grid`].
+/// Positions that can't be on any grid, like negative coordinates, convert to `None`.
+pub trait GridIndex {
+    fn to_xy(self) -> Option<(usize, usize)>;
+}
+
+impl GridIndex for (usize, usize) {
+    fn to_xy(self) -> Option<(usize, usize)> {
+        Some(self)
+    }
+}
+
+impl GridIndex for Vector2<i32> {
+    fn to_xy(self) -> Option<(usize, usize)> {
+        Some((self.x.try_into().ok()?, self.y.try_into().ok()?))
+    }
+}
+
+impl GridIndex for Vector2<i64> {
+    fn to_xy(self) -> Option<(usize, usize)> {
+        Some((self.x.try_into().ok()?, self.y.try_into().ok()?))
+    }
+}
+
+/// Which surrounding cells count as neighbors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// The 4 cells sharing an edge
+    Orthogonal,
+    /// The 4 cells sharing only a corner
+    Diagonal,
+    /// All 8 surrounding cells, the moves of a king in chess
+    King,
+}
+
+impl Connectivity {
+    /// The directions from a cell to its neighbors, clockwise starting at north.
+    pub fn directions(self) -> &'static [Direction] {
+        match self {
+            Connectivity::Orthogonal => &Direction::ORTHOGONAL,
+            Connectivity::Diagonal => &Direction::DIAGONAL,
+            Connectivity::King => &Direction::ALL,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells in row-major order.
+    ///
+    /// Panics if the number of cells doesn't match the dimensions, or if the grid would be empty.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert!(width > 0 && height > 0, "grid must not be empty");
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "cell count must match dimensions"
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses one cell per char. The input must be a non-empty rectangle.
+    pub fn parse(input: &str, mut parse_cell: impl FnMut(char) -> T) -> Result<Self, ParseError> {
+        Self::try_parse(input, "", |ch| Some(parse_cell(ch)))
+    }
+
+    /// Like [`Grid::parse`], but chars for which `parse_cell` returns `None` are reported as
+    /// errors, with `expected` describing what would have been valid.
+    pub fn try_parse(
+        input: &str,
+        expected: &str,
+        mut parse_cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+        for line in lines(input) {
+            let n_cells_before = cells.len();
+            for (idx, ch) in line.char_indices() {
+                let cell = parse_cell(ch).ok_or_else(|| {
+                    ParseError::new(input, &line[idx..idx + ch.len_utf8()], expected)
+                })?;
+                cells.push(cell);
+            }
+            let row_width = cells.len() - n_cells_before;
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(ParseError::new(
+                        input,
+                        line,
+                        format!("a row of width {width}"),
+                    ))
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Self::new(width, height, cells)),
+            _ => Err(ParseError::missing(input, "a grid")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: impl GridIndex) -> bool {
+        self.cell_idx(pos).is_some()
+    }
+
+    pub fn get(&self, pos: impl GridIndex) -> Option<&T> {
+        self.cell_idx(pos).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, pos: impl GridIndex) -> Option<&mut T> {
+        self.cell_idx(pos).map(|idx| &mut self.cells[idx])
+    }
+
+    fn cell_idx(&self, pos: impl GridIndex) -> Option<usize> {
+        let (x, y) = pos.to_xy()?;
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    /// The neighbors of `pos` that lie on the grid, together with the direction leading to them.
+    pub fn neighbors(
+        &self,
+        (x, y): (usize, usize),
+        connectivity: Connectivity,
+    ) -> impl Iterator<Item = ((usize, usize), Direction)> + '_ {
+        let pos = Vector2::new(x as i64, y as i64);
+        connectivity
+            .directions()
+            .iter()
+            .filter_map(move |&direction| {
+                let neighbor = (pos + Vector2::from(direction)).to_xy()?;
+                self.contains(neighbor).then_some((neighbor, direction))
+            })
+    }
+
+    /// The neighbors of `pos` on a grid whose opposite edges are connected, together with the
+    /// direction leading to them. On tiny grids, a cell may be its own neighbor.
+    pub fn wrapping_neighbors(
+        &self,
+        (x, y): (usize, usize),
+        connectivity: Connectivity,
+    ) -> impl Iterator<Item = ((usize, usize), Direction)> + '_ {
+        let pos = Vector2::new(x as i64, y as i64);
+        connectivity.directions().iter().map(move |&direction| {
+            let neighbor = pos + Vector2::from(direction);
+            let x = neighbor.x.rem_euclid(self.width as i64) as usize;
+            let y = neighbor.y.rem_euclid(self.height as i64) as usize;
+            ((x, y), direction)
+        })
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn values(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> std::slice::Chunks<'_, T> {
+        self.cells.chunks(self.width)
+    }
+
+    /// Panics if `x` is outside the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside the grid");
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The cells starting at `start` and repeatedly moving by `step` until leaving the grid.
+    pub fn ray(
+        &self,
+        start: Vector2<i64>,
+        step: Vector2<i64>,
+    ) -> impl Iterator<Item = (Vector2<i64>, &T)> {
+        std::iter::successors(Some(start), move |&pos| Some(pos + step))
+            .map_while(|pos| Some((pos, self.get(pos)?)))
+    }
+
+    /// The diagonals running from top left to bottom right, starting with the one in the
+    /// bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (1..self.height)
+            .rev()
+            .map(|y| (0, y))
+            .chain((0..self.width).map(|x| (x, 0)));
+        starts.map(|(x, y)| {
+            self.ray(Vector2::new(x as i64, y as i64), Vector2::new(1, 1))
+                .map(|(_, cell)| cell)
+        })
+    }
+
+    /// The diagonals running from top right to bottom left, starting with the one in the
+    /// top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.width)
+            .map(|x| (x, 0))
+            .chain((1..self.height).map(|y| (self.width - 1, y)));
+        starts.map(|(x, y)| {
+            self.ray(Vector2::new(x as i64, y as i64), Vector2::new(-1, 1))
+                .map(|(_, cell)| cell)
+        })
+    }
+
+    /// The position of the first cell equal to `value`, searching row by row.
+    pub fn find(&self, value: &T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).next()
+    }
+
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = (usize, usize)> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T, I: GridIndex> Index<I> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: I) -> &T {
+        let idx = self.cell_idx(pos).expect("position out of grid");
+        &self.cells[idx]
+    }
+}
+
+impl<T, I: GridIndex> IndexMut<I> for Grid<T> {
+    fn index_mut(&mut self, pos: I) -> &mut T {
+        let idx = self.cell_idx(pos).expect("position out of grid");
+        &mut self.cells[idx]
+    }
+}
+
+/// Prints the cells row by row, without separators between cells.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+abc
+def
+";
+
+    #[test]
+    fn parse_and_index() {
+        let grid = Grid::parse(EXAMPLE, |ch| ch).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.get(Vector2::new(1i64, 0)), Some(&'b'));
+        assert_eq!(grid.get(Vector2::new(-1i64, 0)), None);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.find(&'e'), Some((1, 1)));
+        assert_eq!(grid.to_string(), EXAMPLE);
+    }
+
+    #[test]
+    fn rows_columns_and_diagonals() {
+        let grid = Grid::parse(EXAMPLE, |ch| ch).unwrap();
+        let collect = |iter: &mut dyn Iterator<Item = &char>| iter.collect::<String>();
+
+        assert_eq!(
+            grid.rows().map(String::from_iter).collect::<Vec<_>>(),
+            ["abc", "def"]
+        );
+        assert_eq!(
+            grid.columns()
+                .map(|mut col| collect(&mut col))
+                .collect::<Vec<_>>(),
+            ["ad", "be", "cf"]
+        );
+        assert_eq!(
+            grid.diagonals()
+                .map(|mut diag| collect(&mut diag))
+                .collect::<Vec<_>>(),
+            ["d", "ae", "bf", "c"]
+        );
+        assert_eq!(
+            grid.anti_diagonals()
+                .map(|mut diag| collect(&mut diag))
+                .collect::<Vec<_>>(),
+            ["a", "bd", "ce", "f"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the grid")]
+    fn column_beyond_the_last() {
+        let grid = Grid::parse(EXAMPLE, |ch| ch).unwrap();
+        // would otherwise start in the next row
+        let _ = grid.column(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "must not be empty")]
+    fn empty_grid() {
+        Grid::<char>::new(0, 5, vec![]);
+    }
+
+    #[test]
+    fn neighbors_at_the_edge() {
+        let grid = Grid::parse(EXAMPLE, |ch| ch).unwrap();
+        let neighbors = |connectivity| {
+            grid.neighbors((0, 0), connectivity)
+                .map(|(pos, _)| grid[pos])
+                .collect::<String>()
+        };
+        assert_eq!(neighbors(Connectivity::Orthogonal), "bd");
+        assert_eq!(neighbors(Connectivity::Diagonal), "e");
+        assert_eq!(neighbors(Connectivity::King), "bed");
+
+        let first_neighbor = grid.neighbors((1, 1), Connectivity::Orthogonal).next();
+        assert_eq!(first_neighbor, Some(((1, 0), Direction::North)));
+    }
+
+    #[test]
+    fn wrapping_neighbors() {
+        let grid = Grid::parse(EXAMPLE, |ch| ch).unwrap();
+        let neighbors = grid
+            .wrapping_neighbors((0, 0), Connectivity::King)
+            .map(|(pos, _)| grid[pos])
+            .collect::<String>();
+        assert_eq!(neighbors, "debedfcf");
+    }
+
+    #[test]
+    fn rejects_ragged_rows_and_invalid_cells() {
+        let err = Grid::parse("ab\nc\n", |ch| ch).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "a row of width 2");
+
+        let err = Grid::try_parse("12\n3x\n", "a digit", |ch| ch.to_digit(10)).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "x"));
+
+        assert!(Grid::parse("\n", |ch| ch).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+//! Requests to the puzzle server, authenticated with the session cookie.
+
+use std::{error::Error, time::Duration};
+
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .user_agent(USER_AGENT)
+        .timeout(Duration::from_secs(30))
+        .build()
+}
+
+pub(crate) fn get(url: &str, session: &str) -> Result<String, Box<dyn Error>> {
+    let request = agent()
+        .get(url)
+        .set("Cookie", &format!("session={session}"));
+    response_text(url, request.call())
+}
+
+pub(crate) fn post_form(
+    url: &str,
+    session: &str,
+    form: &[(&str, &str)],
+) -> Result<String, Box<dyn Error>> {
+    let request = agent()
+        .post(url)
+        .set("Cookie", &format!("session={session}"));
+    response_text(url, request.send_form(form))
+}
+
+fn response_text(
+    url: &str,
+    response: Result<ureq::Response, ureq::Error>,
+) -> Result<String, Box<dyn Error>> {
+    match response {
+        Ok(response) => Ok(response.into_string()?),
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            Err(format!("{url} responded with status {status}: {}", body.trim()).into())
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// A stand-in for the puzzle server in tests.
+#[cfg(test)]
+pub(crate) mod test_server {
+    use crate::config::Config;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::JoinHandle,
+    };
+
+    /// Answers a single request with `status` and `body`. Returns the base URL of the server and
+    /// a handle yielding the request as text.
+    pub(crate) fn serve_once(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut request = String::new();
+            while reader.read_line(&mut request).unwrap() > 2 {}
+            let content_length = request
+                .lines()
+                .find_map(|line| line.strip_prefix("Content-Length: "))
+                .map_or(0, |len| len.parse().unwrap());
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(std::str::from_utf8(&content).unwrap());
+
+            write!(
+                &stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            request
+        });
+        (base_url, server)
+    }
+
+    /// A config pointing at `base_url`, with a fresh data directory named after `name`.
+    pub(crate) fn test_config(base_url: String, name: &str) -> Config {
+        let data_dir = std::env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&data_dir);
+        Config {
+            session: Some("secret".to_owned()),
+            base_url: Some(base_url),
+            data_dir: Some(data_dir),
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use crate::timing::Timings;
+use std::str::FromStr;
+
+pub mod answers;
+pub mod config;
+pub mod cycle;
+pub mod direction;
+pub mod error;
+pub mod fetch;
+mod files;
+pub mod graph;
+pub mod grid;
+mod http;
+pub mod num;
+pub mod parse;
+pub mod region;
+pub mod report;
+pub mod scaffold;
+pub mod submit;
+pub mod timing;
+pub mod watch;
+
+pub use error::ParseError;
+
+/// The year the runner uses unless another one is asked for.
+pub const DEFAULT_YEAR: u16 = 2024;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err("only part 1 and 2 exist".to_owned()),
+        }
+    }
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => f.pad("1"),
+            Part::Two => f.pad("2"),
+        }
+    }
+}
+
+/// The answer a solver computed for one part of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    NotImplemented,
+}
+
+macro_rules! impl_answer_from_int {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(num: $int) -> Self {
+                    Answer::Number(num as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from_int!(u8, u32, u64, usize, i32, i64);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(num) => write!(f, "{num}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::NotImplemented => write!(f, "not yet implemented"),
+        }
+    }
+}
+
+/// Computes the answer for one part of a day from the puzzle input, and reports how long parsing
+/// the input and solving took.
+pub type Solver = fn(&str, Part) -> (Result<Answer, ParseError>, Timings);
+
+/// Registers the solver and bundled input of each implemented day of a year. Invoked in
+/// `src/yYYYY/mod.rs`, which declares each day `N` as the module `dayN` in `dayN.rs`, with its
+/// input in `dayN_input.txt`. A day module has a `parse` function turning the input into
+/// whatever its `solve` function computes the answer of a part from, and lists the parts `solve`
+/// can answer in `IMPLEMENTED`.
+macro_rules! days {
+    ($($nr:literal => $day:ident,)*) => {
+        /// The solvers of all implemented days, ordered by day.
+        pub const DAY_FNS: &[(u8, Solver)] = &[$(($nr, |input, part| {
+            $crate::timing::parse_and_solve(input, part, $day::parse, $day::solve)
+        })),*];
+
+        /// The parts of `day` that have a solution.
+        pub fn implemented_parts(day: u8) -> &'static [$crate::Part] {
+            match day {
+                $($nr => $day::IMPLEMENTED,)*
+                _ => &[],
+            }
+        }
+
+        /// The puzzle input shipped with the crate for `day`, or an empty string if there is none.
+        pub fn bundled_input(day: u8) -> &'static str {
+            match day {
+                $($nr => include_str!(concat!(stringify!($day), "_input.txt")),)*
+                _ => "",
+            }
+        }
+    };
+}
+
+/// Registers each year with solutions. Each year `YYYY` is declared as the module `yYYYY` in
+/// `src/yYYYY/`.
+macro_rules! years {
+    ($($nr:literal => $year:ident,)*) => {
+        /// The solvers of each year, ordered by year.
+        pub const YEARS: &[(u16, &[(u8, Solver)])] = &[$(($nr, $year::DAY_FNS)),*];
+
+        /// The parts of `day` of `year` that have a solution, known without running the solver.
+        pub fn implemented_parts(year: u16, day: u8) -> &'static [Part] {
+            match year {
+                $($nr => $year::implemented_parts(day),)*
+                _ => &[],
+            }
+        }
+
+        /// The puzzle input shipped with the crate for `day` of `year`, or an empty string if
+        /// there is none.
+        pub fn bundled_input(year: u16, day: u8) -> &'static str {
+            match year {
+                $($nr => $year::bundled_input(day),)*
+                _ => "",
+            }
+        }
+    };
+}
+
+pub mod y2024;
+
+// `new <day> --year <year>` adds new years to this list and declares their modules above it.
+years! {
+    2024 => y2024,
+}
+
+/// The solvers of all implemented days of `year`, ordered by day.
+pub fn day_fns(year: u16) -> &'static [(u8, Solver)] {
+    YEARS
+        .iter()
+        .find(|&&(nr, _)| nr == year)
+        .map_or(&[], |&(_, day_fns)| day_fns)
+}
+
+/// The solver registered for `day` of `year`, or one reporting that the day is not implemented
+/// yet.
+pub fn solver(year: u16, day: u8) -> Solver {
+    day_fns(year).iter().find(|&&(nr, _)| nr == day).map_or(
+        |_, _| (Ok(to_be_implemented()), Timings::default()),
+        |&(_, solver)| solver,
+    )
+}
+
+/// Runs the solver for `day` of `year`, tagging parse errors with the day.
+pub fn solve(year: u16, day: u8, input: &str, part: Part) -> Result<Answer, ParseError> {
+    solve_timed(year, day, input, part).0
+}
+
+/// Like [`solve`], but also reports how long parsing and solving took.
+pub fn solve_timed(
+    year: u16,
+    day: u8,
+    input: &str,
+    part: Part,
+) -> (Result<Answer, ParseError>, Timings) {
+    let (answer, timings) = solver(year, day)(input, part);
+    (answer.map_err(|err| err.in_day(day)), timings)
+}
+
+pub fn to_be_implemented() -> Answer {
+    Answer::NotImplemented
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parts_not_declared_as_implemented_are_not() {
+        for &(year, day_fns) in YEARS {
+            for &(day, _) in day_fns {
+                let input = bundled_input(year, day);
+                for part in [Part::One, Part::Two] {
+                    if !implemented_parts(year, day).contains(&part) {
+                        assert_eq!(
+                            solve(year, day, input, part),
+                            Ok(Answer::NotImplemented),
+                            "{year} day {day} part {part}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Exact arithmetic on the decimal digits of numbers.
+
+/// `10^exp`, or `None` if it doesn't fit into a `u64`.
+pub fn pow10(exp: u32) -> Option<u64> {
+    10u64.checked_pow(exp)
+}
+
+/// The number of decimal digits of `num`, counting `0` as one digit.
+pub fn digit_count(num: u64) -> u32 {
+    num.checked_ilog10().map_or(1, |log| log + 1)
+}
+
+/// Splits `num` into the number formed by all but its last `n_low_digits` digits and the number
+/// formed by those digits.
+pub fn split_digits(num: u64, n_low_digits: u32) -> (u64, u64) {
+    match pow10(n_low_digits) {
+        Some(divisor) => (num / divisor, num % divisor),
+        None => (0, num),
+    }
+}
+
+/// Splits `num` into the numbers formed by the first and second half of its digits, or returns
+/// `None` if it has an odd number of digits.
+pub fn split_in_middle(num: u64) -> Option<(u64, u64)> {
+    let n_digits = digit_count(num);
+    n_digits
+        .is_multiple_of(2)
+        .then(|| split_digits(num, n_digits / 2))
+}
+
+/// The number whose digits are those of `high` followed by those of `low`, or `None` on overflow.
+pub fn concat(high: u64, low: u64) -> Option<u64> {
+    if high == 0 {
+        // a leading zero vanishes, even in front of a number too long to be shifted
+        return Some(low);
+    }
+    high.checked_mul(pow10(digit_count(low))?)?.checked_add(low)
+}
+
+/// The decimal digits of `num`, most significant first.
+pub fn digits(num: u64) -> Digits {
+    Digits {
+        num,
+        n_digits: digit_count(num),
+    }
+}
+
+pub struct Digits {
+    num: u64,
+    n_digits: u32,
+}
+
+impl Iterator for Digits {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        self.n_digits = self.n_digits.checked_sub(1)?;
+        let (digit, rest) = split_digits(self.num, self.n_digits);
+        self.num = rest;
+        Some(digit as u8)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.n_digits as usize, Some(self.n_digits as usize))
+    }
+}
+
+impl ExactSizeIterator for Digits {}
+
+/// The number with the digits of `num` in reverse order, dropping leading zeros, or `None` on
+/// overflow.
+pub fn reverse_digits(num: u64) -> Option<u64> {
+    let mut reversed = 0u64;
+    let mut rest = num;
+    loop {
+        reversed = reversed.checked_mul(10)?.checked_add(rest % 10)?;
+        rest /= 10;
+        if rest == 0 {
+            return Some(reversed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `0`, `u64::MAX`, and every power of ten that fits into a `u64` as well as its neighbors.
+    fn boundaries() -> Vec<u64> {
+        let mut nums = vec![0, u64::MAX - 1, u64::MAX];
+        for exp in 0..20 {
+            let power = pow10(exp).unwrap();
+            nums.extend([power - 1, power, power + 1]);
+        }
+        nums
+    }
+
+    #[test]
+    fn count_digits_at_boundaries() {
+        assert_eq!(pow10(19), Some(10_000_000_000_000_000_000));
+        assert_eq!(pow10(20), None);
+        for num in boundaries() {
+            assert_eq!(digit_count(num) as usize, num.to_string().len(), "{num}");
+        }
+    }
+
+    #[test]
+    fn split_at_boundaries() {
+        for num in boundaries() {
+            let text = num.to_string();
+            for n_low_digits in 0..=21 {
+                let split_idx = text.len().saturating_sub(n_low_digits);
+                let (high, low) = text.split_at(split_idx);
+                let expected = (high.parse().unwrap_or(0), low.parse().unwrap_or(0));
+                assert_eq!(split_digits(num, n_low_digits as u32), expected, "{num}");
+            }
+
+            let expected = (text.len() % 2 == 0).then(|| {
+                let (high, low) = text.split_at(text.len() / 2);
+                (high.parse().unwrap(), low.parse().unwrap())
+            });
+            assert_eq!(split_in_middle(num), expected, "{num}");
+        }
+    }
+
+    #[test]
+    fn concat_at_boundaries() {
+        for high in boundaries() {
+            for low in boundaries() {
+                let expected = format!("{high}{low}").parse::<u64>().ok();
+                assert_eq!(concat(high, low), expected, "{high} {low}");
+            }
+        }
+        assert_eq!(concat(1844674407370955161, 5), Some(u64::MAX));
+        assert_eq!(concat(1844674407370955161, 6), None);
+    }
+
+    #[test]
+    fn digits_and_reversal_at_boundaries() {
+        for num in boundaries() {
+            let text = num.to_string();
+            let expected = text.bytes().map(|byte| byte - b'0').collect::<Vec<_>>();
+            assert_eq!(digits(num).len(), expected.len());
+            assert_eq!(digits(num).collect::<Vec<_>>(), expected);
+
+            let reversed = text.chars().rev().collect::<String>();
+            assert_eq!(reverse_digits(num), reversed.parse().ok(), "{num}");
+        }
+    }
+}
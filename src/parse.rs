@@ -0,0 +1,281 @@
+//! Parsing puzzle input without copying it. Everything works on slices of the input, so that
+//! errors can point at the offending text, and integers are scanned directly from the bytes.
+
+use crate::error::ParseError;
+use std::marker::PhantomData;
+
+/// The lines of `input` without line endings (`\n` or `\r\n`), ignoring trailing blank lines.
+pub fn lines(input: &str) -> std::str::Lines<'_> {
+    input.trim_end_matches(['\r', '\n']).lines()
+}
+
+/// Like [`str::split_once`], but reports a missing `separator` as an error.
+pub fn split_once<'a>(
+    input: &str,
+    text: &'a str,
+    separator: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    text.split_once(separator)
+        .ok_or_else(|| ParseError::new(input, text, format!("`{separator}`")))
+}
+
+/// Splits `text`, a slice of `input`, at every `separator` into exactly `N` fields.
+pub fn fields<'a, const N: usize>(
+    input: &str,
+    text: &'a str,
+    separator: &str,
+) -> Result<[&'a str; N], ParseError> {
+    let error = || {
+        ParseError::new(
+            input,
+            text,
+            format!("{N} fields separated by `{separator}`"),
+        )
+    };
+    let mut parts = text.split(separator);
+    let mut fields = [""; N];
+    for field in &mut fields {
+        *field = parts.next().ok_or_else(error)?;
+    }
+    match parts.next() {
+        Some(_) => Err(error()),
+        None => Ok(fields),
+    }
+}
+
+/// Parses every char of `text`, a slice of `input`, as a decimal digit.
+pub fn parse_digits(input: &str, text: &str) -> Result<Vec<u8>, ParseError> {
+    text.char_indices()
+        .map(|(idx, ch)| {
+            ch.to_digit(10)
+                .map(|digit| digit as u8)
+                .ok_or_else(|| ParseError::new(input, &text[idx..idx + ch.len_utf8()], "a digit"))
+        })
+        .collect()
+}
+
+/// A primitive integer type that can be scanned from decimal digits.
+pub trait Int: Copy {
+    const ZERO: Self;
+    /// Whether a leading `-` is part of the number.
+    const SIGNED: bool;
+
+    /// Appends a decimal digit, moving away from zero in the direction given by `negative`.
+    /// Returns `None` on overflow.
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_int {
+    ($($int:ty: $signed:literal),*) => {
+        $(impl Int for $int {
+            const ZERO: Self = 0;
+            const SIGNED: bool = $signed;
+
+            fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+                let shifted = self.checked_mul(10)?;
+                if negative {
+                    shifted.checked_sub(digit as Self)
+                } else {
+                    shifted.checked_add(digit as Self)
+                }
+            }
+        })*
+    };
+}
+
+impl_int!(
+    u8: false, u16: false, u32: false, u64: false, u128: false, usize: false,
+    i8: true, i16: true, i32: true, i64: true, i128: true, isize: true
+);
+
+/// Scans the integer at the start of `text`: a `-` for signed types, followed by digits. Returns
+/// the number and the rest of `text`, or `None` if `text` doesn't start with a number that fits
+/// into `T`.
+pub fn leading_int<T: Int>(text: &str) -> Option<(T, &str)> {
+    let bytes = text.as_bytes();
+    let negative = T::SIGNED && bytes.first() == Some(&b'-');
+    let digits_start = negative as usize;
+
+    let mut num = T::ZERO;
+    let mut end = digits_start;
+    while let Some(&byte) = bytes.get(end).filter(|byte| byte.is_ascii_digit()) {
+        num = num.push_digit(byte - b'0', negative)?;
+        end += 1;
+    }
+    (end > digits_start).then(|| (num, &text[end..]))
+}
+
+/// Parses `text`, a slice of `input`, as an integer.
+pub fn int<T: Int>(input: &str, text: &str) -> Result<T, ParseError> {
+    match leading_int(text) {
+        Some((num, "")) => Ok(num),
+        _ => Err(ParseError::new(input, text, "a number")),
+    }
+}
+
+/// Iterates over all integers in `text`, a slice of `input`, skipping anything between them.
+/// Fails on the first number that doesn't fit into `T`.
+pub fn ints<'a, T: Int>(input: &'a str, text: &'a str) -> Ints<'a, T> {
+    Ints {
+        input,
+        rest: text,
+        int_type: PhantomData,
+    }
+}
+
+pub struct Ints<'a, T> {
+    input: &'a str,
+    rest: &'a str,
+    int_type: PhantomData<T>,
+}
+
+impl<T: Int> Iterator for Ints<'_, T> {
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.rest.as_bytes();
+        let start = bytes.iter().enumerate().position(|(idx, byte)| {
+            byte.is_ascii_digit()
+                || (T::SIGNED
+                    && *byte == b'-'
+                    && bytes.get(idx + 1).is_some_and(u8::is_ascii_digit))
+        })?;
+        let text = &self.rest[start..];
+        match leading_int(text) {
+            Some((num, rest)) => {
+                self.rest = rest;
+                Some(Ok(num))
+            }
+            None => {
+                let len = 1 + text[1..].bytes().take_while(u8::is_ascii_digit).count();
+                self.rest = "";
+                Some(Err(ParseError::new(
+                    self.input,
+                    &text[..len],
+                    format!("a number that fits into {}", std::any::type_name::<T>()),
+                )))
+            }
+        }
+    }
+}
+
+/// Rows of varying length kept back to back in a single buffer, so that collecting them doesn't
+/// allocate once per row.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Rows<T> {
+    values: Vec<T>,
+    /// Where each row ends in `values`
+    ends: Vec<usize>,
+}
+
+impl<T> Rows<T> {
+    pub fn new() -> Self {
+        Rows {
+            values: vec![],
+            ends: vec![],
+        }
+    }
+
+    /// Appends a row. On the first error in `row`, the rows are left as they were.
+    pub fn try_push<E>(&mut self, row: impl IntoIterator<Item = Result<T, E>>) -> Result<(), E> {
+        let start = self.values.len();
+        for value in row {
+            match value {
+                Ok(value) => self.values.push(value),
+                Err(err) => {
+                    self.values.truncate(start);
+                    return Err(err);
+                }
+            }
+        }
+        self.ends.push(self.values.len());
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &[T]> {
+        let starts = std::iter::once(0).chain(self.ends.iter().copied());
+        starts
+            .zip(&self.ends)
+            .map(|(start, &end)| &self.values[start..end])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_integers() {
+        assert_eq!(leading_int::<u32>("123abc"), Some((123, "abc")));
+        assert_eq!(leading_int::<u32>("-1"), None);
+        assert_eq!(leading_int::<i32>("-12 3"), Some((-12, " 3")));
+        assert_eq!(leading_int::<i8>("-128"), Some((-128, "")));
+        assert_eq!(leading_int::<i8>("128"), None);
+        assert_eq!(
+            leading_int::<u64>("18446744073709551615"),
+            Some((u64::MAX, ""))
+        );
+        assert_eq!(leading_int::<u64>("18446744073709551616"), None);
+        assert_eq!(leading_int::<i64>("-"), None);
+
+        let input = "12 x3";
+        assert_eq!(int::<u8>(input, &input[..2]), Ok(12));
+        let err = int::<u8>(input, &input[3..]).unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (4, "x3"));
+    }
+
+    #[test]
+    fn all_integers_in_a_line() {
+        let input = "Button A: X+94, Y-34";
+        let signed = ints::<i64>(input, input).collect::<Result<Vec<_>, _>>();
+        assert_eq!(signed, Ok(vec![94, -34]));
+        let unsigned = ints::<u64>(input, input).collect::<Result<Vec<_>, _>>();
+        assert_eq!(unsigned, Ok(vec![94, 34]));
+
+        let input = "1 300 2";
+        let mut bytes = ints::<u8>(input, input);
+        assert_eq!(bytes.next(), Some(Ok(1)));
+        let err = bytes.next().unwrap().unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (3, "300"));
+        assert_eq!(bytes.next(), None);
+    }
+
+    #[test]
+    fn split_into_fields() {
+        let input = "1|2|3";
+        assert_eq!(fields(input, input, "|"), Ok(["1", "2", "3"]));
+        assert!(fields::<2>(input, input, "|").is_err());
+        assert!(fields::<4>(input, input, "|").is_err());
+    }
+
+    #[test]
+    fn lines_with_crlf() {
+        let input = "a\r\nb\n\r\nc\r\n\r\n";
+        assert_eq!(lines(input).collect::<Vec<_>>(), ["a", "b", "", "c"]);
+    }
+
+    #[test]
+    fn rows_of_varying_length() {
+        let mut rows = Rows::new();
+        let row = |values: &[u8]| {
+            values
+                .iter()
+                .map(|&value| Ok::<_, ()>(value))
+                .collect::<Vec<_>>()
+        };
+        rows.try_push(row(&[1, 2])).unwrap();
+        rows.try_push(row(&[])).unwrap();
+        assert_eq!(rows.try_push([Ok(3), Err(())]), Err(()));
+        rows.try_push(row(&[4])).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows.iter().collect::<Vec<_>>(), [&[1, 2][..], &[], &[4]]);
+    }
+}
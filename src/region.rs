@@ -0,0 +1,214 @@
+//! Splitting a grid into regions of equal cells that are connected orthogonally, and measuring
+//! their shapes.
+
+use crate::{
+    direction::Direction,
+    graph::bfs,
+    grid::{Connectivity, Grid},
+};
+use nalgebra::Vector2;
+
+/// A partition of the elements `0..n` into disjoint sets that can be merged (union-find).
+#[derive(Debug, Clone)]
+pub struct DisjointSets {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl DisjointSets {
+    /// Puts every element into a set of its own.
+    pub fn new(n_elements: usize) -> Self {
+        DisjointSets {
+            parents: (0..n_elements).collect(),
+            sizes: vec![1; n_elements],
+        }
+    }
+
+    /// The element representing the set containing `element`.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        // shortcut the path, so that the next lookup is fast
+        let mut element = element;
+        while element != root {
+            element = std::mem::replace(&mut self.parents[element], root);
+        }
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`. Returns whether they were separate before.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        // attaching the smaller tree keeps the paths short
+        let (small, large) = if self.sizes[a] < self.sizes[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parents[small] = large;
+        self.sizes[large] += self.sizes[small];
+        true
+    }
+
+    /// The number of elements in the set containing `element`.
+    pub fn size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+}
+
+/// The positions of all cells equal to the one at `start` that can be reached from it by
+/// orthogonal steps over equal cells, in breadth-first order.
+pub fn flood_fill<T: PartialEq>(grid: &Grid<T>, start: (usize, usize)) -> Vec<(usize, usize)> {
+    let value = &grid[start];
+    bfs([start], |&pos| {
+        grid.neighbors(pos, Connectivity::Orthogonal)
+            .filter(|&(neighbor, _)| grid[neighbor] == *value)
+            .map(|(neighbor, _)| neighbor)
+    })
+    .map(|(pos, _)| pos)
+    .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// The first cell of the region, row by row
+    pub start: (usize, usize),
+    pub area: usize,
+    /// The number of cell edges between the region and other cells or the outside of the grid
+    pub perimeter: usize,
+    /// The number of straight sections of the boundary, including those around holes
+    pub sides: usize,
+    /// The smallest `x` and `y` of any cell in the region
+    pub top_left: (usize, usize),
+    /// The largest `x` and `y` of any cell in the region
+    pub bottom_right: (usize, usize),
+}
+
+/// A grid split into regions, see [`label_regions`].
+#[derive(Debug, Clone)]
+pub struct Regions {
+    /// For each cell, the index of its region in `regions`
+    pub labels: Grid<usize>,
+    /// Ordered by their first cell, row by row
+    pub regions: Vec<Region>,
+}
+
+/// Splits `grid` into maximal regions of equal cells that are connected orthogonally.
+pub fn label_regions<T: PartialEq>(grid: &Grid<T>) -> Regions {
+    let cell_idx = |(x, y): (usize, usize)| y * grid.width() + x;
+
+    // merging with the cells to the east and south covers every pair of neighbors once
+    let mut sets = DisjointSets::new(grid.width() * grid.height());
+    for (pos, value) in grid.iter() {
+        let later_neighbors = grid
+            .neighbors(pos, Connectivity::Orthogonal)
+            .filter(|&(_, direction)| matches!(direction, Direction::East | Direction::South));
+        for (neighbor, _) in later_neighbors {
+            if grid[neighbor] == *value {
+                sets.union(cell_idx(pos), cell_idx(neighbor));
+            }
+        }
+    }
+
+    let mut label_of_set = vec![None; grid.width() * grid.height()];
+    let mut regions = vec![];
+    let labels = grid.positions().map(|pos| {
+        let set = sets.find(cell_idx(pos));
+        *label_of_set[set].get_or_insert_with(|| {
+            regions.push(Region {
+                start: pos,
+                area: 0,
+                perimeter: 0,
+                sides: 0,
+                top_left: pos,
+                bottom_right: pos,
+            });
+            regions.len() - 1
+        })
+    });
+    let labels = Grid::new(grid.width(), grid.height(), labels.collect());
+
+    for ((x, y), &label) in labels.iter() {
+        let pos = Vector2::new(x as i64, y as i64);
+        let in_region = |offset: Vector2<i64>| labels.get(pos + offset) == Some(&label);
+        let region = &mut regions[label];
+        region.area += 1;
+        region.top_left = (region.top_left.0.min(x), region.top_left.1.min(y));
+        region.bottom_right = (region.bottom_right.0.max(x), region.bottom_right.1.max(y));
+
+        for direction in Direction::ORTHOGONAL {
+            let side = Vector2::from(direction);
+            let next_side = Vector2::from(direction.turn_right());
+            if !in_region(side) {
+                region.perimeter += 1;
+            }
+            // A polygon has as many sides as corners. The corner of this cell between `side` and
+            // the next side clockwise is a corner of the boundary if both neighbors there are
+            // outside, or both are inside but the cell diagonally between them is outside.
+            let is_corner = match (in_region(side), in_region(next_side)) {
+                (false, false) => true,
+                (true, true) => !in_region(side + next_side),
+                _ => false,
+            };
+            region.sides += is_corner as usize;
+        }
+    }
+
+    Regions { labels, regions }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_sets() {
+        let mut sets = DisjointSets::new(5);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(3, 4));
+        assert!(sets.union(1, 4));
+        assert!(!sets.union(0, 3));
+        assert_eq!(sets.find(0), sets.find(4));
+        assert_ne!(sets.find(0), sets.find(2));
+        assert_eq!((sets.size(3), sets.size(2)), (4, 1));
+    }
+
+    #[test]
+    fn regions_with_holes() {
+        let grid = Grid::parse("AAAA\nABBA\nAAAA\nCAAC\n", |ch| ch).unwrap();
+        let Regions { labels, regions } = label_regions(&grid);
+        assert_eq!(labels.to_string(), "0000\n0110\n0000\n2003\n");
+
+        let outer = &regions[0];
+        assert_eq!((outer.start, outer.area, outer.perimeter), ((0, 0), 12, 22));
+        // 8 outside, counting the notches at the bottom corners, and 4 around the hole
+        assert_eq!(outer.sides, 12);
+        assert_eq!((outer.top_left, outer.bottom_right), ((0, 0), (3, 3)));
+
+        let hole = &regions[1];
+        assert_eq!((hole.area, hole.perimeter, hole.sides), (2, 6, 4));
+        assert_eq!((hole.top_left, hole.bottom_right), ((1, 1), (2, 1)));
+        assert_eq!(regions.len(), 4);
+
+        let filled = flood_fill(&grid, (1, 3));
+        assert_eq!(filled.len(), outer.area);
+        assert!(filled.iter().all(|&pos| labels[pos] == 0));
+    }
+
+    #[test]
+    fn huge_uniform_region() {
+        // far more cells than a recursive fill could handle on a test thread's stack
+        let grid = Grid::filled(300, 300, '.');
+        let Regions { regions, .. } = label_regions(&grid);
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].area, 90_000);
+        assert_eq!((regions[0].perimeter, regions[0].sides), (1200, 4));
+        assert_eq!(flood_fill(&grid, (150, 150)).len(), 90_000);
+    }
+}
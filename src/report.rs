@@ -0,0 +1,97 @@
+//! Machine-readable reports of solver runs, one JSON object per day and part.
+
+use crate::{timing::Timings, Answer, ParseError, Part};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Ok,
+    NotImplemented,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunReport {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+    /// Always a string, so that large numbers survive JSON parsers using floats.
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    pub parse_ms: f64,
+    pub solve_ms: f64,
+    /// Parsing and solving together
+    pub duration_ms: f64,
+}
+
+impl RunReport {
+    pub fn new(
+        year: u16,
+        day: u8,
+        part: Part,
+        result: &Result<Answer, ParseError>,
+        timings: Timings,
+    ) -> Self {
+        let (status, answer, error) = match result {
+            Ok(Answer::NotImplemented) => (Status::NotImplemented, None, None),
+            Ok(answer) => (Status::Ok, Some(answer.to_string()), None),
+            Err(err) => (Status::Error, None, Some(err.to_string())),
+        };
+        RunReport {
+            year,
+            day,
+            part: part.into(),
+            status,
+            answer,
+            error,
+            parse_ms: timings.parse.as_secs_f64() * 1000.0,
+            solve_ms: timings.solve.as_secs_f64() * 1000.0,
+            duration_ms: timings.total().as_secs_f64() * 1000.0,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("reports consist of plain data")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn json_report() {
+        let report = RunReport::new(
+            2024,
+            7,
+            Part::Two,
+            &Ok(Answer::from(92148721834692u64)),
+            Timings {
+                parse: Duration::from_micros(250),
+                solve: Duration::from_micros(1250),
+            },
+        );
+        assert_eq!(
+            report.to_json(),
+            r#"{"year":2024,"day":7,"part":2,"status":"ok","answer":"92148721834692","error":null,"parse_ms":0.25,"solve_ms":1.25,"duration_ms":1.5}"#
+        );
+        assert_eq!(RunReport::from_json(&report.to_json()).unwrap(), report);
+
+        let report = RunReport::new(
+            2024,
+            12,
+            Part::Two,
+            &Ok(Answer::NotImplemented),
+            Timings::default(),
+        );
+        assert_eq!(report.status, Status::NotImplemented);
+        assert_eq!(report.answer, None);
+    }
+}
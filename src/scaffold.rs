@@ -0,0 +1,278 @@
+//! Generating the files for a new day and registering it in the `days!` list of its year. The
+//! module of a year that has no days yet is generated as well and registered in the `years!` list
+//! in `lib.rs`.
+
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+/// The source directory of this crate, where the day modules live.
+pub const SRC_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+const TEMPLATE: &str = r#"use crate::{to_be_implemented, Answer, ParseError, Part};
+
+pub const IMPLEMENTED: &[Part] = &[];
+
+pub fn parse(input: &str) -> Result<&str, ParseError> {
+    Ok(input)
+}
+
+pub fn solve(_input: &str, part: Part) -> Result<Answer, ParseError> {
+    match part {
+        Part::One => Ok(to_be_implemented()),
+        Part::Two => Ok(to_be_implemented()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("dayN_example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve(parse(EXAMPLE).unwrap(), Part::One), Ok(to_be_implemented()));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve(parse(EXAMPLE).unwrap(), Part::Two), Ok(to_be_implemented()));
+    }
+}
+"#;
+
+const YEAR_TEMPLATE: &str = r#"//! Solutions of the YYYY event.
+
+use crate::Solver;
+
+// `new <day>` adds new days to this list and declares their modules above it.
+days! {
+}
+"#;
+
+/// Creates the module, an empty input and an empty example file for `day` of `year` in
+/// `src_dir/yYYYY` and registers the day there, creating and registering the module of the year
+/// if needed. Returns the paths of the files that were created.
+///
+/// Fails without touching anything if the module already exists.
+pub fn new_day(src_dir: &Path, year: u16, day: u8) -> io::Result<Vec<PathBuf>> {
+    let year_dir = src_dir.join(format!("y{year}"));
+    let module = year_dir.join(format!("day{day}.rs"));
+    if module.exists() {
+        return Err(io::Error::new(
+            ErrorKind::AlreadyExists,
+            format!("{} already exists", module.display()),
+        ));
+    }
+
+    // All contents are built before anything is written, and the lists registering the new
+    // modules are updated last, so that a failure never leaves a registered module missing.
+    let year_module = year_dir.join("mod.rs");
+    let lib_path = src_dir.join("lib.rs");
+    let (year_source, lib) = if year_module.exists() {
+        (fs::read_to_string(&year_module)?, None)
+    } else {
+        let lib = fs::read_to_string(&lib_path)?;
+        let lib = register_year(&lib, year).ok_or_else(|| missing_list("years!", &lib_path))?;
+        (YEAR_TEMPLATE.replace("YYYY", &year.to_string()), Some(lib))
+    };
+    let year_source =
+        register_day(&year_source, day).ok_or_else(|| missing_list("days!", &year_module))?;
+    let module_source = TEMPLATE.replace("dayN", &format!("day{day}"));
+
+    let input = year_dir.join(format!("day{day}_input.txt"));
+    let example = year_dir.join(format!("day{day}_example.txt"));
+    fs::create_dir_all(&year_dir)?;
+    fs::write(&module, module_source)?;
+    for path in [&input, &example] {
+        if !path.exists() {
+            fs::write(path, "")?;
+        }
+    }
+    fs::write(&year_module, year_source)?;
+
+    let mut created = vec![];
+    if let Some(lib) = lib {
+        fs::write(&lib_path, lib)?;
+        created.push(year_module);
+    }
+    created.extend([module, input, example]);
+    Ok(created)
+}
+
+fn missing_list(list: &str, path: &Path) -> io::Error {
+    io::Error::new(
+        ErrorKind::InvalidData,
+        format!("could not find the `{list}` list in {}", path.display()),
+    )
+}
+
+/// Declares the module of `day` in the source code of a year module and adds it to the `days!`
+/// invocation, keeping both sorted. Returns `None` if the list can't be found.
+pub fn register_day(source: &str, day: u8) -> Option<String> {
+    let source = declare(source, "days!", "day", day.into())?;
+    register(&source, "days!", day.into(), &format!("day{day}"))
+}
+
+/// Declares the module of `year` in the source code of `lib.rs` and adds it to the `years!`
+/// invocation, keeping both sorted. Returns `None` if the list can't be found.
+pub fn register_year(lib: &str, year: u16) -> Option<String> {
+    let lib = declare(lib, "years!", "y", year)?;
+    register(&lib, "years!", year, &format!("y{year}"))
+}
+
+/// Adds `pub mod <prefix><nr>;` among the declarations of the other numbered modules. The first
+/// one goes above the comment introducing `list`.
+fn declare(source: &str, list: &str, prefix: &str, nr: u16) -> Option<String> {
+    let declaration_start = format!("pub mod {prefix}");
+    let declared_nr = |line: &str| {
+        let nr = line
+            .strip_prefix(&declaration_start)?
+            .trim_end()
+            .strip_suffix(';')?;
+        nr.parse::<u16>().ok()
+    };
+
+    // after the last declaration with a smaller number, or else before the first one
+    let mut after_smaller = None;
+    let mut first = None;
+    let mut offset = 0;
+    for line in source.split_inclusive('\n') {
+        if let Some(declared_nr) = declared_nr(line) {
+            if declared_nr == nr {
+                return Some(source.to_owned());
+            }
+            first.get_or_insert(offset);
+            if declared_nr < nr {
+                after_smaller = Some(offset + line.len());
+            }
+        }
+        offset += line.len();
+    }
+
+    let declaration = format!("{declaration_start}{nr};\n");
+    let mut source = source.to_owned();
+    match after_smaller.or(first) {
+        Some(offset) => source.insert_str(offset, &declaration),
+        None => {
+            let mut offset = source.find(&format!("\n{list} {{\n"))? + 1;
+            while let Some(line_start) = source[..offset]
+                .strip_suffix('\n')
+                .map(|before| before.rfind('\n').map_or(0, |idx| idx + 1))
+                .filter(|&line_start| source[line_start..].starts_with("//"))
+            {
+                offset = line_start;
+            }
+            source.insert_str(offset, &format!("{declaration}\n"));
+        }
+    }
+    Some(source)
+}
+
+fn register(source: &str, list: &str, nr: u16, module: &str) -> Option<String> {
+    let list_header = format!("\n{list} {{\n");
+    let list_start = source.find(&list_header)? + list_header.len();
+    let list_len = source[list_start..].find('}')?;
+    let entry = format!("    {nr} => {module},\n");
+
+    let mut offset = list_start;
+    for line in source[list_start..list_start + list_len].split_inclusive('\n') {
+        let registered_nr = line.trim().split_once(" =>")?.0.parse::<u16>().ok()?;
+        if registered_nr == nr {
+            return Some(source.to_owned());
+        }
+        if registered_nr > nr {
+            break;
+        }
+        offset += line.len();
+    }
+
+    let mut source = source.to_owned();
+    source.insert_str(offset, &entry);
+    Some(source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YEAR_MODULE: &str = "\
+pub mod day1;
+pub mod day3;
+
+// `new <day>` adds new days to this list.
+days! {
+    1 => day1,
+    3 => day3,
+}
+";
+
+    #[test]
+    fn registers_days_in_order() {
+        let source = register_day(YEAR_MODULE, 2).unwrap();
+        assert_eq!(
+            source,
+            YEAR_MODULE
+                .replace("pub mod day3;", "pub mod day2;\npub mod day3;")
+                .replace("    3 => day3,", "    2 => day2,\n    3 => day3,")
+        );
+        let source = register_day(&source, 4).unwrap();
+        assert!(source.contains("pub mod day3;\npub mod day4;\n\n"));
+        assert!(source.ends_with("    3 => day3,\n    4 => day4,\n}\n"));
+        assert_eq!(register_day(&source, 4).unwrap(), source);
+
+        let source = register_day(&source, 0).unwrap();
+        assert!(source.starts_with("pub mod day0;\npub mod day1;\n"));
+    }
+
+    #[test]
+    fn registers_years() {
+        let lib = "pub mod y2024;\n\nyears! {\n    2024 => y2024,\n}\n";
+        assert_eq!(
+            register_year(lib, 2023).unwrap(),
+            "pub mod y2023;\npub mod y2024;\n\n\
+             years! {\n    2023 => y2023,\n    2024 => y2024,\n}\n"
+        );
+
+        let year_module = YEAR_TEMPLATE.replace("YYYY", "2023");
+        let year_module = register_day(&year_module, 5).unwrap();
+        assert!(year_module.contains(
+            "use crate::Solver;\n\npub mod day5;\n\n// `new <day>` adds new days to this list"
+        ));
+        assert!(year_module.ends_with("days! {\n    5 => day5,\n}\n"));
+    }
+
+    #[test]
+    fn missing_list() {
+        assert_eq!(register_day("fn main() {}\n", 2), None);
+        assert_eq!(register_year("fn main() {}\n", 2023), None);
+    }
+
+    #[test]
+    fn new_day_in_new_year() {
+        let src_dir = std::env::temp_dir().join(format!("scaffold-{}", std::process::id()));
+        fs::create_dir_all(&src_dir).unwrap();
+        let lib_path = src_dir.join("lib.rs");
+
+        // nothing is written if a list is missing
+        fs::write(&lib_path, "fn main() {}\n").unwrap();
+        assert!(new_day(&src_dir, 2030, 1).is_err());
+        assert!(!src_dir.join("y2030").exists());
+
+        fs::write(&lib_path, "\nyears! {\n    2024 => y2024,\n}\n").unwrap();
+        let created = new_day(&src_dir, 2030, 1).unwrap();
+        assert_eq!(created.len(), 4);
+        assert!(created.iter().all(|path| path.exists()));
+        let lib = fs::read_to_string(&lib_path).unwrap();
+        assert!(lib.contains("pub mod y2030;\n\n"));
+        assert!(lib.contains("    2030 => y2030,\n"));
+        let year_module = fs::read_to_string(src_dir.join("y2030/mod.rs")).unwrap();
+        assert!(year_module.contains("pub mod day1;\n"));
+        assert!(year_module.contains("    1 => day1,\n"));
+
+        fs::remove_dir_all(&src_dir).unwrap();
+    }
+}
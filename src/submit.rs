@@ -0,0 +1,368 @@
+//! Submitting answers to the puzzle server. Every response is recorded in a history file in the
+//! data directory, so that answers known to be wrong are never sent twice and the cooldown the
+//! server imposes after each answer is respected.
+
+use crate::{config::Config, files::read_if_exists, http, Part};
+use serde::{Deserialize, Serialize};
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The part was already solved, so the server didn't check the answer.
+    AlreadySolved,
+}
+
+impl Verdict {
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Verdict::Right => "right",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::AlreadySolved => "already solved",
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Response {
+    /// `None` if the answer came too soon after the previous one and wasn't checked.
+    pub verdict: Option<Verdict>,
+    /// How long the server refuses further answers.
+    pub cooldown: Duration,
+}
+
+/// Reads the verdict and cooldown out of the HTML page the server responds with.
+pub fn parse_response(html: &str) -> Option<Response> {
+    if let Some((_, minutes, seconds)) =
+        lazy_regex::regex_captures!(r"You have (?:(\d+)m )?(\d+)s left to wait", html)
+    {
+        let minutes = minutes.parse().unwrap_or(0);
+        let seconds: u64 = seconds.parse().ok()?;
+        return Some(Response {
+            verdict: None,
+            cooldown: Duration::from_secs(minutes * 60 + seconds),
+        });
+    }
+
+    let verdict = if html.contains("That's the right answer") {
+        Verdict::Right
+    } else if html.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else if html.contains("That's not the right answer") {
+        if html.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if html.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else {
+        return None;
+    };
+    let cooldown_minutes =
+        match lazy_regex::regex_captures!(r"(?i)wait (one|\d+) minutes? before trying again", html)
+        {
+            Some((_, "one")) => 1,
+            Some((_, minutes)) => minutes.parse().ok()?,
+            None => 0,
+        };
+    Some(Response {
+        verdict: Some(verdict),
+        cooldown: Duration::from_secs(cooldown_minutes * 60),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    pub submissions: Vec<Submission>,
+    /// Seconds since the Unix epoch until which the server refuses answers.
+    pub blocked_until: u64,
+}
+
+/// Where the history of submissions is kept.
+pub fn history_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("submissions.json")
+}
+
+fn unix_time(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+impl History {
+    /// Reads the history at `path`. A missing file counts as no submissions.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        match read_if_exists(path)? {
+            Some(text) => Ok(serde_json::from_str(&text)?),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    /// What is already known about `answer` without asking the server: its own verdict if it was
+    /// submitted before, `Wrong` if another answer was right, or `TooHigh`/`TooLow` if it lies
+    /// beyond an answer that was too high or too low.
+    pub fn known_verdict(&self, year: u16, day: u8, part: Part, answer: &str) -> Option<Verdict> {
+        let submissions = self
+            .submissions
+            .iter()
+            .filter(|s| s.year == year && s.day == day && s.part == u8::from(part))
+            .filter(|s| s.verdict != Verdict::AlreadySolved);
+        let number = answer.parse::<i128>().ok();
+
+        let mut known = None;
+        for submission in submissions {
+            if submission.answer == answer {
+                return Some(submission.verdict);
+            }
+            let bound = submission.answer.parse::<i128>().ok();
+            known = match (submission.verdict, number, bound) {
+                (Verdict::Right, ..) => Some(Verdict::Wrong),
+                (Verdict::TooHigh, Some(number), Some(bound)) if number >= bound => {
+                    known.or(Some(Verdict::TooHigh))
+                }
+                (Verdict::TooLow, Some(number), Some(bound)) if number <= bound => {
+                    known.or(Some(Verdict::TooLow))
+                }
+                _ => known,
+            };
+        }
+        known
+    }
+
+    /// How long the server still refuses answers at `now`.
+    pub fn cooldown_left(&self, now: SystemTime) -> Option<Duration> {
+        let left = self.blocked_until.saturating_sub(unix_time(now));
+        (left > 0).then(|| Duration::from_secs(left))
+    }
+
+    pub fn record(
+        &mut self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+        response: &Response,
+        now: SystemTime,
+    ) {
+        let now = unix_time(now);
+        self.blocked_until = self.blocked_until.max(now + response.cooldown.as_secs());
+        if let Some(verdict) = response.verdict {
+            self.submissions.push(Submission {
+                year,
+                day,
+                part: part.into(),
+                answer: answer.to_owned(),
+                verdict,
+                time: now,
+            });
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The server checked the answer.
+    Submitted(Verdict),
+    /// The answer wasn't sent, because the history already tells its verdict.
+    Known(Verdict),
+}
+
+/// Posts `answer` for `day` and `part` of `year`, unless the history already tells its verdict.
+/// Fails if the server still refuses answers, or responds that the previous answer was too recent.
+pub fn submit(
+    config: &Config,
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: &str,
+) -> Result<Outcome, Box<dyn Error>> {
+    let path = history_path(config.data_dir());
+    let mut history = History::load(&path)?;
+    if let Some(verdict) = history.known_verdict(year, day, part, answer) {
+        return Ok(Outcome::Known(verdict));
+    }
+    if let Some(left) = history.cooldown_left(SystemTime::now()) {
+        return Err(format!("the server refuses answers for another {}s", left.as_secs()).into());
+    }
+
+    let url = format!("{}/{year}/day/{day}/answer", config.base_url());
+    let level = u8::from(part).to_string();
+    let html = http::post_form(
+        &url,
+        config.session()?,
+        &[("level", &level), ("answer", answer)],
+    )?;
+    let response = parse_response(&html)
+        .ok_or_else(|| format!("could not understand the response of {url}:\n{html}"))?;
+    history.record(year, day, part, answer, &response, SystemTime::now());
+    history.save(&path)?;
+
+    match response.verdict {
+        Some(verdict) => Ok(Outcome::Submitted(verdict)),
+        None => Err(format!(
+            "answered too recently, the server refuses answers for another {}s",
+            response.cooldown.as_secs()
+        )
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::test_server::{serve_once, test_config};
+
+    const RIGHT: &str = "<article><p>That's the right answer! You are one gold star closer to \
+        finding the Chief Historian.</p></article>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high. \
+        If you're stuck, make sure you're using the full input data. Please wait one minute \
+        before trying again.</p></article>";
+    const TOO_SOON: &str = "<article><p>You gave an answer too recently; you have to wait after \
+        submitting an answer before trying again.  You have 1m 5s left to wait.</p></article>";
+
+    #[test]
+    fn parse_responses() {
+        assert_eq!(
+            parse_response(RIGHT),
+            Some(Response {
+                verdict: Some(Verdict::Right),
+                cooldown: Duration::ZERO
+            })
+        );
+        assert_eq!(
+            parse_response(TOO_HIGH),
+            Some(Response {
+                verdict: Some(Verdict::TooHigh),
+                cooldown: Duration::from_secs(60)
+            })
+        );
+        assert_eq!(
+            parse_response(TOO_SOON),
+            Some(Response {
+                verdict: None,
+                cooldown: Duration::from_secs(65)
+            })
+        );
+        let wrong = "That's not the right answer. Please wait 5 minutes before trying again.";
+        assert_eq!(
+            parse_response(wrong),
+            Some(Response {
+                verdict: Some(Verdict::Wrong),
+                cooldown: Duration::from_secs(300)
+            })
+        );
+        assert_eq!(parse_response("<html>Log in</html>"), None);
+    }
+
+    #[test]
+    fn known_verdicts() {
+        let mut history = History::default();
+        let now = SystemTime::now();
+        let too_high = parse_response(TOO_HIGH).unwrap();
+        history.record(2024, 1, Part::One, "100", &too_high, now);
+        assert_eq!(
+            history.known_verdict(2024, 1, Part::One, "100"),
+            Some(Verdict::TooHigh)
+        );
+        assert_eq!(
+            history.known_verdict(2024, 1, Part::One, "150"),
+            Some(Verdict::TooHigh)
+        );
+        assert_eq!(history.known_verdict(2024, 1, Part::One, "99"), None);
+        assert_eq!(history.known_verdict(2024, 1, Part::Two, "100"), None);
+        assert_eq!(history.known_verdict(2023, 1, Part::One, "100"), None);
+        assert!(history.cooldown_left(now).is_some());
+        assert_eq!(history.cooldown_left(now + Duration::from_secs(60)), None);
+
+        history.record(
+            2024,
+            1,
+            Part::One,
+            "42",
+            &parse_response(RIGHT).unwrap(),
+            now,
+        );
+        assert_eq!(
+            history.known_verdict(2024, 1, Part::One, "42"),
+            Some(Verdict::Right)
+        );
+        assert_eq!(
+            history.known_verdict(2024, 1, Part::One, "43"),
+            Some(Verdict::Wrong)
+        );
+    }
+
+    #[test]
+    fn submits_answers_once() {
+        let (base_url, server) = serve_once("200 OK", RIGHT);
+        let config = test_config(base_url, "submit");
+
+        assert_eq!(
+            submit(&config, 2024, 5, Part::Two, "1234").unwrap(),
+            Outcome::Submitted(Verdict::Right)
+        );
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/5/answer HTTP/1.1\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=1234"));
+
+        // the server is gone, so these only succeed if nothing is sent
+        assert_eq!(
+            submit(&config, 2024, 5, Part::Two, "1234").unwrap(),
+            Outcome::Known(Verdict::Right)
+        );
+        assert_eq!(
+            submit(&config, 2024, 5, Part::Two, "1000").unwrap(),
+            Outcome::Known(Verdict::Wrong)
+        );
+        std::fs::remove_dir_all(config.data_dir()).unwrap();
+    }
+
+    #[test]
+    fn honours_cooldown() {
+        let (base_url, server) = serve_once("200 OK", TOO_SOON);
+        let config = test_config(base_url, "submit-cooldown");
+
+        let err = submit(&config, 2024, 5, Part::One, "1").unwrap_err();
+        assert!(err.to_string().starts_with("answered too recently"));
+        server.join().unwrap();
+
+        let err = submit(&config, 2024, 5, Part::One, "1").unwrap_err();
+        assert!(err.to_string().starts_with("the server refuses answers"));
+        std::fs::remove_dir_all(config.data_dir()).unwrap();
+    }
+}
@@ -0,0 +1,93 @@
+//! Measuring how long solvers take.
+
+use crate::{Answer, ParseError, Part, Solver};
+use std::time::{Duration, Instant};
+
+/// How long the two steps of running a solver took.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+    /// Turning the puzzle input into the form the parts are solved from
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Timings {
+    pub fn total(self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+/// Parses `input` and solves `part` from the result, timing both steps separately. Nothing is
+/// solved if parsing fails.
+pub fn parse_and_solve<'a, T>(
+    input: &'a str,
+    part: Part,
+    parse: impl FnOnce(&'a str) -> Result<T, ParseError>,
+    solve: impl FnOnce(T, Part) -> Result<Answer, ParseError>,
+) -> (Result<Answer, ParseError>, Timings) {
+    let (parsed, parse_duration) = timed(|| parse(input));
+    let mut timings = Timings {
+        parse: parse_duration,
+        solve: Duration::ZERO,
+    };
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(err) => return (Err(err), timings),
+    };
+    let (answer, solve_duration) = timed(|| solve(parsed, part));
+    timings.solve = solve_duration;
+    (answer, timings)
+}
+
+/// Runs `f` and returns its result together with how long it took.
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Runs `f` in a thread pool of its own with `n_threads` threads, so that tests can compare
+/// parallel solvers on different thread counts.
+#[cfg(test)]
+pub(crate) fn solve_on_threads<T: Send>(n_threads: usize, f: impl FnOnce() -> T + Send) -> T {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(n_threads)
+        .build()
+        .expect("the test thread pool can be built")
+        .install(f)
+}
+
+pub struct BenchStats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+/// Runs `solver` `runs` times on the same input and collects the durations of parsing and
+/// solving together.
+///
+/// Panics if `runs` is 0.
+pub fn bench(
+    solver: Solver,
+    input: &str,
+    part: Part,
+    runs: usize,
+) -> Result<(Answer, BenchStats), ParseError> {
+    assert!(runs > 0, "need at least one run to benchmark");
+
+    let mut answer = None;
+    let mut durations = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let (run_answer, timings) = solver(input, part);
+        answer = Some(run_answer?);
+        durations.push(timings.total());
+    }
+    durations.sort();
+
+    let stats = BenchStats {
+        min: durations[0],
+        median: durations[runs / 2],
+        max: durations[runs - 1],
+    };
+    Ok((answer.unwrap(), stats))
+}
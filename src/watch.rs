@@ -0,0 +1,93 @@
+//! Polling files for changes, so that a day can be re-run whenever one of its files is saved.
+
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+/// Modification time and length of a file, `None` if it doesn't exist.
+type Stamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &Path) -> Stamp {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+pub struct FileWatcher {
+    files: Vec<(PathBuf, Stamp)>,
+}
+
+impl FileWatcher {
+    /// Starts watching `paths`. Files that don't exist yet are reported once they are created.
+    pub fn new(paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let stamp = stamp(&path);
+                (path, stamp)
+            })
+            .collect();
+        FileWatcher { files }
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|(path, _)| path.as_path())
+    }
+
+    /// The files that were modified, created or deleted since the previous call.
+    pub fn changed_files(&mut self) -> Vec<PathBuf> {
+        let mut changed = vec![];
+        for (path, old_stamp) in &mut self.files {
+            let new_stamp = stamp(path);
+            if new_stamp != *old_stamp {
+                *old_stamp = new_stamp;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+
+    /// Blocks until files change, checking every `interval`. Changes within one more interval are
+    /// reported together, since editors often write a file in several steps.
+    pub fn wait(&mut self, interval: Duration) -> Vec<PathBuf> {
+        loop {
+            std::thread::sleep(interval);
+            let mut changed = self.changed_files();
+            if !changed.is_empty() {
+                std::thread::sleep(interval);
+                for path in self.changed_files() {
+                    if !changed.contains(&path) {
+                        changed.push(path);
+                    }
+                }
+                return changed;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_changes() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let [existing, missing] = ["input.txt", "example.txt"].map(|name| dir.join(name));
+        std::fs::write(&existing, "1").unwrap();
+        let _ = std::fs::remove_file(&missing);
+
+        let mut watcher = FileWatcher::new([existing.clone(), missing.clone()]);
+        assert!(watcher.changed_files().is_empty());
+
+        std::fs::write(&existing, "12").unwrap();
+        assert_eq!(watcher.changed_files(), std::slice::from_ref(&existing));
+        assert!(watcher.changed_files().is_empty());
+
+        std::fs::write(&missing, "").unwrap();
+        std::fs::remove_file(&existing).unwrap();
+        assert_eq!(watcher.wait(Duration::from_millis(1)), [existing, missing]);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
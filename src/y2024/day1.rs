@@ -0,0 +1,94 @@
+use crate::{
+    parse::{fields, int, lines},
+    Answer, ParseError, Part,
+};
+use std::collections::HashMap;
+
+pub const IMPLEMENTED: &[Part] = &[Part::One, Part::Two];
+
+/// The location IDs of the left and the right list.
+pub fn parse(input: &str) -> Result<(Vec<i64>, Vec<i64>), ParseError> {
+    let (mut left, mut right) = (vec![], vec![]);
+    for line in lines(input) {
+        let [left_id, right_id] = fields(input, line, "   ")?;
+        left.push(int::<i64>(input, left_id)?);
+        right.push(int::<i64>(input, right_id)?);
+    }
+    Ok((left, right))
+}
+
+pub fn solve(
+    (mut left, mut right): (Vec<i64>, Vec<i64>),
+    part: Part,
+) -> Result<Answer, ParseError> {
+    match part {
+        Part::One => {
+            left.sort();
+            right.sort();
+            let difference = left
+                .iter()
+                .zip(&right)
+                // the problem description is actually missing what we need to do if the difference
+                // is negative.
+                .map(|(l, r)| (r - l).abs())
+                .sum::<i64>();
+            Ok(difference.into())
+        }
+        Part::Two => {
+            fn count_occurences(list: Vec<i64>) -> HashMap<i64, i64> {
+                let mut counts = HashMap::new();
+                for n in list {
+                    *counts.entry(n).or_insert(0) += 1;
+                }
+                counts
+            }
+
+            let left_counts = count_occurences(left);
+            let right_counts = count_occurences(right);
+            let similarity_score = left_counts
+                .into_iter()
+                .map(|(num, count)| num * count * right_counts.get(&num).cloned().unwrap_or(0))
+                .sum::<i64>();
+            Ok(similarity_score.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::One),
+            Ok(Answer::from(11))
+        );
+    }
+
+    #[test]
+    fn part1_example_with_crlf_and_trailing_blank_lines() {
+        let input = EXAMPLE.replace('\n', "\r\n") + "\r\n\n";
+        assert_eq!(
+            solve(parse(&input).unwrap(), Part::One),
+            Ok(Answer::from(11))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::Two),
+            Ok(Answer::from(31))
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{
+    graph::{bfs, count_paths},
+    grid::{Connectivity, Grid},
+    Answer, ParseError, Part,
+};
+use rayon::prelude::*;
+
+pub const IMPLEMENTED: &[Part] = &[Part::One, Part::Two];
+
+/// The height of each cell.
+pub fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::try_parse(input, "a digit", |ch| ch.to_digit(10).map(|d| d as u8))
+}
+
+pub fn solve(map: Grid<u8>, part: Part) -> Result<Answer, ParseError> {
+    let map = &map;
+
+    let uphill_neighbors = |&pos: &(usize, usize)| {
+        map.neighbors(pos, Connectivity::Orthogonal)
+            .filter(move |&(neighbor, _)| map[neighbor] == map[pos] + 1)
+            .map(|(neighbor, _)| neighbor)
+    };
+    let is_peak = |&pos: &(usize, usize)| map[pos] == 9;
+
+    let trailheads = map.find_all(&0).collect::<Vec<_>>();
+    let solution = trailheads
+        .into_par_iter()
+        .map(|trailhead| match part {
+            Part::One => bfs([trailhead], uphill_neighbors)
+                .filter(|(pos, _)| is_peak(pos))
+                .count() as u64,
+            // every step leads uphill, so trails can't run in circles
+            Part::Two => count_paths(trailhead, uphill_neighbors, is_peak).unwrap(),
+        })
+        .sum::<u64>();
+    Ok(solution.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timing::solve_on_threads;
+
+    const EXAMPLE: &str = "\
+89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::One),
+            Ok(Answer::from(36))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::Two),
+            Ok(Answer::from(81))
+        );
+    }
+
+    #[test]
+    fn same_answers_on_one_and_several_threads() {
+        for part in [Part::One, Part::Two] {
+            let run = || solve(parse(EXAMPLE).unwrap(), part);
+            let sequential = solve_on_threads(1, run);
+            assert!(matches!(sequential, Ok(Answer::Number(_))));
+            assert_eq!(solve_on_threads(4, run), sequential);
+        }
+    }
+}
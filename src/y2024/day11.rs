@@ -0,0 +1,108 @@
+use crate::{num::split_in_middle, parse::int, Answer, ParseError, Part};
+use std::collections::HashMap;
+
+pub const IMPLEMENTED: &[Part] = &[Part::One, Part::Two];
+
+/// The numbers engraved on the stones.
+pub fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+    input
+        .split_ascii_whitespace()
+        .map(|stone| int::<u64>(input, stone))
+        .collect()
+}
+
+pub fn solve(stones: Vec<u64>, part: Part) -> Result<Answer, ParseError> {
+    let n_blinks = match part {
+        Part::One => 25,
+        Part::Two => 75,
+    };
+    Ok(count_stones(&stones, n_blinks).into())
+}
+
+/// The number of stones `stone_nr` turns into after blinking `n_blinking_left` more times.
+/// `counts` remembers the results by blinks left and stone, since the same stones come up over
+/// and over again.
+fn visit_generated_stones(
+    counts: &mut HashMap<(u8, u64), u64>,
+    n_blinking_left: u8,
+    stone_nr: u64,
+) -> u64 {
+    if n_blinking_left == 0 {
+        return 1;
+    }
+    if let Some(&count) = counts.get(&(n_blinking_left, stone_nr)) {
+        return count;
+    }
+    let count = if stone_nr == 0 {
+        visit_generated_stones(counts, n_blinking_left - 1, 1)
+    } else if let Some((num1, num2)) = split_in_middle(stone_nr) {
+        visit_generated_stones(counts, n_blinking_left - 1, num1)
+            + visit_generated_stones(counts, n_blinking_left - 1, num2)
+    } else {
+        visit_generated_stones(counts, n_blinking_left - 1, stone_nr * 2024)
+    };
+    counts.insert((n_blinking_left, stone_nr), count);
+    count
+}
+
+/// The number of stones after blinking `n_blinks` times.
+fn count_stones(stones: &[u64], n_blinks: u8) -> u64 {
+    // kept per call, so that every run (and benchmark) does the full work
+    let mut counts = HashMap::new();
+    stones
+        .iter()
+        .map(|&stone_nr| visit_generated_stones(&mut counts, n_blinks, stone_nr))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "125 17\n";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::One),
+            Ok(Answer::from(55312))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::Two),
+            Ok(Answer::from(65601038650482u64))
+        );
+    }
+
+    /// Applies the rules to every stone of the row, one blink at a time.
+    fn simulate_blinks(stones: &[u64], n_blinks: u8) -> u64 {
+        let mut stones = stones.to_vec();
+        for _ in 0..n_blinks {
+            stones = stones
+                .into_iter()
+                .flat_map(|stone_nr| match split_in_middle(stone_nr) {
+                    _ if stone_nr == 0 => vec![1],
+                    Some((num1, num2)) => vec![num1, num2],
+                    None => vec![stone_nr * 2024],
+                })
+                .collect();
+        }
+        stones.len() as u64
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn memoized_count_matches_simulation(
+            stones in proptest::collection::vec(0..1_000_000u64, 1..6),
+            n_blinks in 0..16u8,
+        ) {
+            proptest::prop_assert_eq!(
+                count_stones(&stones, n_blinks),
+                simulate_blinks(&stones, n_blinks)
+            );
+        }
+    }
+}
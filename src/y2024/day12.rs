@@ -0,0 +1,95 @@
+use crate::{
+    grid::Grid,
+    region::{label_regions, Region},
+    Answer, ParseError, Part,
+};
+
+pub const IMPLEMENTED: &[Part] = &[Part::One, Part::Two];
+
+/// The plant type of each garden plot.
+pub fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse(input, |ch| ch)
+}
+
+pub fn solve(map: Grid<char>, part: Part) -> Result<Answer, ParseError> {
+    let price = |region: &Region| match part {
+        Part::One => region.area * region.perimeter,
+        // bulk discount
+        Part::Two => region.area * region.sides,
+    };
+    let total_price = label_regions(&map).regions.iter().map(price).sum::<usize>();
+    Ok(total_price.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_SMALL: &str = "\
+AAAA
+BBCD
+BBCC
+EEEC
+";
+
+    const EXAMPLE: &str = "\
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE
+";
+
+    #[test]
+    fn part1_example_small() {
+        assert_eq!(
+            solve(parse(EXAMPLE_SMALL).unwrap(), Part::One),
+            Ok(Answer::from(140))
+        );
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::One),
+            Ok(Answer::from(1930))
+        );
+    }
+
+    #[test]
+    fn part2_example_small() {
+        assert_eq!(
+            solve(parse(EXAMPLE_SMALL).unwrap(), Part::Two),
+            Ok(Answer::from(80))
+        );
+    }
+
+    #[test]
+    fn part2_example_with_holes() {
+        let input = "\
+AAAAAA
+AAABBA
+AAABBA
+ABBAAA
+ABBAAA
+AAAAAA
+";
+        assert_eq!(
+            solve(parse(input).unwrap(), Part::Two),
+            Ok(Answer::from(368))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::Two),
+            Ok(Answer::from(1206))
+        );
+    }
+}
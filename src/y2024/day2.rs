@@ -0,0 +1,85 @@
+use crate::{
+    parse::{int, lines, Rows},
+    Answer, ParseError, Part,
+};
+use itertools::Itertools;
+
+pub const IMPLEMENTED: &[Part] = &[Part::One, Part::Two];
+
+/// The levels of each report.
+pub fn parse(input: &str) -> Result<Rows<i64>, ParseError> {
+    let mut reports = Rows::new();
+    for line in lines(input) {
+        reports.try_push(
+            line.split_ascii_whitespace()
+                .map(|level| int::<i64>(input, level)),
+        )?;
+    }
+    Ok(reports)
+}
+
+pub fn solve(reports: Rows<i64>, part: Part) -> Result<Answer, ParseError> {
+    fn report_is_safe(report: impl Iterator<Item = i64>) -> bool {
+        let mut monotonically_increasing = true;
+        let mut monotonically_decreasing = true;
+        // let mut is_slowly_changing = true;
+
+        for (n1, n2) in report.tuple_windows() {
+            match n2 - n1 {
+                -3..=-1 => monotonically_increasing = false,
+                0 => return false, // not changing
+                1..=3 => monotonically_decreasing = false,
+                _ => return false, // changing too quickly
+            }
+        }
+
+        monotonically_increasing || monotonically_decreasing
+    }
+
+    let mut n_safe = 0;
+    for report in reports.iter() {
+        let is_safe = match part {
+            Part::One => report_is_safe(report.iter().copied()),
+            Part::Two => (0..report.len()).any(|skipped_idx| {
+                let partial_report = report
+                    .iter()
+                    .enumerate()
+                    .filter(|&(idx, _)| idx != skipped_idx)
+                    .map(|(_, &level)| level);
+                report_is_safe(partial_report)
+            }),
+        };
+        n_safe += is_safe as usize;
+    }
+    Ok(n_safe.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::One),
+            Ok(Answer::from(2))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::Two),
+            Ok(Answer::from(4))
+        );
+    }
+}
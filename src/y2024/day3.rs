@@ -0,0 +1,72 @@
+use crate::{
+    parse::{fields, int},
+    Answer, ParseError, Part,
+};
+
+pub const IMPLEMENTED: &[Part] = &[Part::One, Part::Two];
+
+pub enum Instruction {
+    Mul(i64, i64),
+    Do,
+    Dont,
+}
+
+/// The instructions hidden in the corrupted memory, in order.
+pub fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    // finding the matches is much cheaper than capturing the numbers in them
+    let regex = lazy_regex::regex!(r"mul\(\d{1,3},\d{1,3}\)|do\(\)|don't\(\)");
+    regex
+        .find_iter(input)
+        .map(|m| match m.as_str() {
+            "do()" => Ok(Instruction::Do),
+            "don't()" => Ok(Instruction::Dont),
+            // assuming recursive calls like mul(mul(1,1),2) are not valid
+            mul => {
+                let [num1, num2] = fields(input, &mul[4..mul.len() - 1], ",")?;
+                Ok(Instruction::Mul(int(input, num1)?, int(input, num2)?))
+            }
+        })
+        .collect()
+}
+
+pub fn solve(instructions: Vec<Instruction>, part: Part) -> Result<Answer, ParseError> {
+    let mut sum = 0;
+    let mut is_enabled = true;
+    for instruction in instructions {
+        match instruction {
+            Instruction::Mul(num1, num2) if is_enabled => sum += num1 * num2,
+            Instruction::Mul(..) => {}
+            Instruction::Do => is_enabled = true,
+            // only part 2 heeds don't()
+            Instruction::Dont => is_enabled = part == Part::One,
+        }
+    }
+    Ok(sum.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_PART1: &str =
+        "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))\n";
+
+    const EXAMPLE_PART2: &str =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))\n";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE_PART1).unwrap(), Part::One),
+            Ok(Answer::from(161))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE_PART2).unwrap(), Part::Two),
+            Ok(Answer::from(48))
+        );
+    }
+}
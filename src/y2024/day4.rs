@@ -0,0 +1,93 @@
+use crate::{
+    grid::{Connectivity, Grid},
+    Answer, ParseError, Part,
+};
+use nalgebra::Vector2;
+
+pub const IMPLEMENTED: &[Part] = &[Part::One, Part::Two];
+
+/// The letters of the word search.
+pub fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse(input, |ch| ch)
+}
+
+pub fn solve(letter_matrix: Grid<char>, part: Part) -> Result<Answer, ParseError> {
+    match part {
+        Part::One => {
+            let mut count = 0;
+            for (x, y) in letter_matrix.positions() {
+                let start = Vector2::new(x as i64, y as i64);
+                for &direction in Connectivity::King.directions() {
+                    let word_found = letter_matrix
+                        .ray(start, direction.into())
+                        .take(4)
+                        .map(|(_, &letter)| letter)
+                        .eq("XMAS".chars());
+                    if word_found {
+                        count += 1;
+                    }
+                }
+            }
+            Ok(count.into())
+        }
+        Part::Two => {
+            let mut x_mas_count = 0;
+
+            for (x, y) in letter_matrix.find_all(&'A') {
+                let center = Vector2::new(x as i64, y as i64);
+                let diagonal_contains_mas = |offset: Vector2<i64>| {
+                    let mut n_m = 0;
+                    let mut n_s = 0;
+
+                    for pos in [center + offset, center - offset] {
+                        n_m += (letter_matrix.get(pos) == Some(&'M')) as u8;
+                        n_s += (letter_matrix.get(pos) == Some(&'S')) as u8;
+                    }
+                    n_m == 1 && n_s == 1
+                };
+
+                if diagonal_contains_mas(Vector2::new(1, 1))
+                    && diagonal_contains_mas(Vector2::new(1, -1))
+                {
+                    x_mas_count += 1;
+                }
+            }
+
+            Ok(x_mas_count.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::One),
+            Ok(Answer::from(18))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::Two),
+            Ok(Answer::from(9))
+        );
+    }
+}
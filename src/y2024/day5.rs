@@ -0,0 +1,158 @@
+use crate::{
+    graph::topological_sort,
+    parse::{int, lines, split_once},
+    Answer, ParseError, Part,
+};
+use std::collections::{HashMap, HashSet};
+
+pub const IMPLEMENTED: &[Part] = &[Part::One, Part::Two];
+
+/// The page ordering rules and the updates of the safety manual.
+pub struct Manual<'a> {
+    input: &'a str,
+    /// The pages that have to come after each page
+    rules: HashMap<u64, HashSet<u64>>,
+    /// The pages of each update, together with the line listing them
+    updates: Vec<(&'a str, Vec<u64>)>,
+}
+
+pub fn parse(input: &str) -> Result<Manual<'_>, ParseError> {
+    let mut input_lines = lines(input);
+    let mut rules = HashMap::new();
+    // rules and page lists are separated by a blank line
+    for line in input_lines.by_ref().take_while(|line| !line.is_empty()) {
+        let (before, after) = split_once(input, line, "|")?;
+        rules
+            .entry(int::<u64>(input, before)?)
+            .or_insert(HashSet::new())
+            .insert(int(input, after)?);
+    }
+
+    let updates = input_lines
+        .map(|line| {
+            let page_list = line
+                .split(',')
+                .map(|page| int::<u64>(input, page))
+                .collect::<Result<Vec<_>, _>>()?;
+            // what is the middle page, if list doesn't have oddly numbered count?
+            if page_list.len() % 2 == 0 {
+                return Err(ParseError::new(input, line, "an odd number of pages"));
+            }
+            Ok((line, page_list))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Manual {
+        input,
+        rules,
+        updates,
+    })
+}
+
+pub fn solve(manual: Manual, part: Part) -> Result<Answer, ParseError> {
+    let Manual {
+        input,
+        rules,
+        updates,
+    } = manual;
+    let mut sorted_lists = vec![];
+    let mut unsorted_lists = vec![];
+    for (line, page_list) in updates {
+        let mut already_seen = HashSet::new();
+        let correctly_ordered = page_list.iter().all(|&page| {
+            already_seen.insert(page);
+            let rule = rules.get(&page);
+            rule.is_none_or(|pages_after| already_seen.is_disjoint(pages_after))
+        });
+        if correctly_ordered {
+            sorted_lists.push(page_list);
+        } else {
+            unsorted_lists.push((line, page_list));
+        }
+    }
+
+    match part {
+        Part::One => {
+            let middle_page_sum = sorted_lists
+                .into_iter()
+                .map(|list| list[list.len() / 2])
+                .sum::<u64>();
+            Ok(middle_page_sum.into())
+        }
+        Part::Two => {
+            let mut middle_page_sum = 0;
+            for (line, page_list) in unsorted_lists {
+                // only the rules between pages of the list matter, so cycles through other pages
+                // don't prevent ordering it
+                let pages_after = |page: &u64| rules.get(page).into_iter().flatten().copied();
+                let sorted_list = topological_sort(page_list, pages_after).map_err(|_| {
+                    ParseError::new(input, line, "an update whose pages can be ordered")
+                })?;
+                middle_page_sum += sorted_list[sorted_list.len() / 2];
+            }
+            Ok(middle_page_sum.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::One),
+            Ok(Answer::from(143))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::Two),
+            Ok(Answer::from(123))
+        );
+    }
+
+    #[test]
+    fn part2_rejects_cyclic_rules() {
+        let input = "1|2\n2|3\n3|1\n\n4,5,6\n1,2,3\n";
+        let err = solve(parse(input).unwrap(), Part::Two).unwrap_err();
+        assert_eq!((err.line, err.text.as_str()), (6, "1,2,3"));
+        assert_eq!(
+            err.to_string(),
+            "line 6, column 1: expected an update whose pages can be ordered, found `1,2,3`"
+        );
+    }
+}
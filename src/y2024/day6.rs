@@ -0,0 +1,161 @@
+use crate::{
+    cycle::{first_repeated, StateSet},
+    direction::Direction,
+    grid::Grid,
+    Answer, ParseError, Part,
+};
+use nalgebra::Vector2;
+use rayon::prelude::*;
+
+pub const IMPLEMENTED: &[Part] = &[Part::One, Part::Two];
+
+/// The lab map and where the guard starts.
+pub struct Lab {
+    blocked_cells: Grid<bool>,
+    /// The starting position and direction of the guard
+    guard: (Vector2<i32>, Direction),
+}
+
+pub fn parse(input: &str) -> Result<Lab, ParseError> {
+    let map = Grid::parse(input, |ch| ch)?;
+    let ((x, y), direction) = map
+        .iter()
+        .find_map(|(pos, &ch)| Some((pos, Direction::from_arrow(ch)?)))
+        .ok_or_else(|| ParseError::missing(input, "a guard (`^`, `>`, `v` or `<`)"))?;
+    Ok(Lab {
+        blocked_cells: map.map(|&ch| ch == '#'),
+        guard: (Vector2::new(x as i32, y as i32), direction),
+    })
+}
+
+/// The next position and direction of the guard, or `None` once the guard leaves the map.
+fn step(
+    blocked_cells: &Grid<bool>,
+    &(pos, direction): &(Vector2<i32>, Direction),
+) -> Option<(Vector2<i32>, Direction)> {
+    let next_pos = pos + Vector2::from(direction);
+    if *blocked_cells.get(next_pos)? {
+        Some((pos, direction.turn_right()))
+    } else {
+        Some((next_pos, direction))
+    }
+}
+
+/// For each cell, a bitset of the directions the guard has walked in through it. Much faster
+/// than hashing the states.
+struct VisitedDirections(Grid<u8>);
+
+impl StateSet<(Vector2<i32>, Direction)> for VisitedDirections {
+    fn insert(&mut self, &(pos, direction): &(Vector2<i32>, Direction)) -> bool {
+        let visited = &mut self.0[pos];
+        let is_new = *visited & direction.bit() == 0;
+        *visited |= direction.bit();
+        is_new
+    }
+}
+
+/// Walks the guard until it leaves the map or walks in circles, which it does if a state repeats.
+fn patrol(
+    blocked_cells: &Grid<bool>,
+    guard: (Vector2<i32>, Direction),
+) -> (bool, VisitedDirections) {
+    let mut visited = VisitedDirections(blocked_cells.map(|_| 0));
+    let repeated = first_repeated(guard, |state| step(blocked_cells, state), &mut visited);
+    (repeated.is_some(), visited)
+}
+
+pub fn solve(lab: Lab, part: Part) -> Result<Answer, ParseError> {
+    let Lab {
+        blocked_cells,
+        guard,
+    } = lab;
+
+    // a guard walking in circles has seen the whole route once a state repeats
+    let (_, VisitedDirections(undisturbed_route)) = patrol(&blocked_cells, guard);
+    let undisturbed_route_visited = undisturbed_route.map(|&directions| directions != 0);
+
+    match part {
+        Part::One => {
+            let n_visited = undisturbed_route_visited
+                .values()
+                .filter(|&&visited| visited)
+                .count();
+            Ok(n_visited.into())
+        }
+        Part::Two => {
+            let (start, _) = guard;
+            let obstruction_candidates = undisturbed_route_visited
+                .find_all(&true)
+                .filter(|&(x, y)| !(x == start.x as usize && y == start.y as usize))
+                .collect::<Vec<_>>();
+
+            // each thread places the candidates into its own copy of the map
+            let n_possible_cycles = obstruction_candidates
+                .into_par_iter()
+                .map_init(
+                    || blocked_cells.clone(),
+                    |blocked_cells, candidate| {
+                        blocked_cells[candidate] = true;
+                        let (is_cyclic, _) = patrol(blocked_cells, guard);
+                        blocked_cells[candidate] = false;
+                        is_cyclic
+                    },
+                )
+                .filter(|&is_cyclic| is_cyclic)
+                .count();
+
+            Ok(n_possible_cycles.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timing::solve_on_threads;
+
+    const EXAMPLE: &str = "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::One),
+            Ok(Answer::from(41))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::Two),
+            Ok(Answer::from(6))
+        );
+    }
+
+    #[test]
+    fn guard_walking_in_circles() {
+        let input = ".#..\n...#\n#^..\n..#.\n";
+        assert_eq!(solve(parse(input).unwrap(), Part::One), Ok(Answer::from(4)));
+    }
+
+    #[test]
+    fn same_answers_on_one_and_several_threads() {
+        for part in [Part::One, Part::Two] {
+            let run = || solve(parse(EXAMPLE).unwrap(), part);
+            let sequential = solve_on_threads(1, run);
+            assert!(matches!(sequential, Ok(Answer::Number(_))));
+            assert_eq!(solve_on_threads(4, run), sequential);
+        }
+    }
+}
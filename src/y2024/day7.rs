@@ -0,0 +1,101 @@
+use crate::{
+    num::concat,
+    parse::{int, lines, split_once, Rows},
+    Answer, ParseError, Part,
+};
+
+pub const IMPLEMENTED: &[Part] = &[Part::One, Part::Two];
+
+/// The desired result and the numbers of each equation.
+pub fn parse(input: &str) -> Result<(Vec<u64>, Rows<u64>), ParseError> {
+    let mut desired_results = vec![];
+    let mut numbers = Rows::new();
+    for line in lines(input) {
+        let (desired_result, numbers_text) = split_once(input, line, ": ")?;
+        desired_results.push(int::<u64>(input, desired_result)?);
+        if numbers_text.trim().is_empty() {
+            return Err(ParseError::new(input, numbers_text, "at least one number"));
+        }
+        numbers.try_push(
+            numbers_text
+                .split_ascii_whitespace()
+                .map(|num| int::<u64>(input, num)),
+        )?;
+    }
+    Ok((desired_results, numbers))
+}
+
+pub fn solve(
+    (desired_results, numbers): (Vec<u64>, Rows<u64>),
+    part: Part,
+) -> Result<Answer, ParseError> {
+    // DFS of all possibilities
+    fn result_can_be_reached(
+        allowed_operations: &[fn(u64, u64) -> Option<u64>],
+        desired_result: u64,
+        intermediate_result: u64,
+        remaining_numbers: &[u64],
+    ) -> bool {
+        if let Some((&next_num, rest)) = remaining_numbers.split_first() {
+            for &operation in allowed_operations {
+                // results that overflow can't be the desired result anymore
+                let Some(next_result) = operation(intermediate_result, next_num) else {
+                    continue;
+                };
+                if result_can_be_reached(allowed_operations, desired_result, next_result, rest) {
+                    return true;
+                }
+            }
+            false
+        } else {
+            desired_result == intermediate_result
+        }
+    }
+
+    let allowed_operations = match part {
+        Part::One => &[u64::checked_add, u64::checked_mul][..],
+        Part::Two => &[u64::checked_add, u64::checked_mul, concat],
+    };
+    let mut total_calibration_result = 0;
+    for (desired_result, numbers) in desired_results.into_iter().zip(numbers.iter()) {
+        // parsing made sure there is at least one number
+        let (&first_num, rest) = numbers.split_first().unwrap();
+        if result_can_be_reached(allowed_operations, desired_result, first_num, rest) {
+            total_calibration_result += desired_result;
+        }
+    }
+    Ok(total_calibration_result.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::One),
+            Ok(Answer::from(3749))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::Two),
+            Ok(Answer::from(11387))
+        );
+    }
+}
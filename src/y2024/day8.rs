@@ -0,0 +1,99 @@
+use crate::{grid::Grid, Answer, ParseError, Part};
+use nalgebra::Vector2;
+use std::collections::HashMap;
+
+pub const IMPLEMENTED: &[Part] = &[Part::One, Part::Two];
+
+/// The antenna frequency of each cell, if there is one.
+pub fn parse(input: &str) -> Result<Grid<Option<char>>, ParseError> {
+    Grid::parse(
+        input,
+        |ch| if ch.is_alphanumeric() { Some(ch) } else { None },
+    )
+}
+
+pub fn solve(board: Grid<Option<char>>, part: Part) -> Result<Answer, ParseError> {
+    let mut antinodes = board.map(|_| false);
+
+    let mut antenna_positions = HashMap::new();
+    for ((x, y), antenna) in board.iter() {
+        if let Some(antenna) = antenna {
+            antenna_positions
+                .entry(antenna)
+                .or_insert(vec![])
+                .push(Vector2::new(x as i64, y as i64));
+        }
+    }
+
+    for (_, antenna_positions) in antenna_positions.iter() {
+        for (antenna_nr, pos2) in antenna_positions.iter().enumerate().skip(1) {
+            for pos1 in &antenna_positions[..antenna_nr] {
+                let diff = pos2 - pos1;
+
+                match part {
+                    Part::One => {
+                        for antinode in [pos2 + diff, pos1 - diff] {
+                            if let Some(is_antinode) = antinodes.get_mut(antinode) {
+                                *is_antinode = true;
+                            }
+                        }
+                    }
+                    Part::Two => {
+                        for (pos, direction) in [(*pos1, -diff), (*pos2, diff)] {
+                            let line = board.ray(pos, direction).map(|(pos, _)| pos);
+                            for pos in line {
+                                antinodes[pos] = true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let n_antinode_locations = antinodes
+        .values()
+        .filter(|&&is_antinode| is_antinode)
+        .count();
+    Ok(n_antinode_locations.into())
+
+    // print board
+    // let printout = board.map(|antenna| antenna.unwrap_or('.'));
+    // println!("{printout}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::One),
+            Ok(Answer::from(14))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::Two),
+            Ok(Answer::from(34))
+        );
+    }
+}
@@ -0,0 +1,233 @@
+use crate::{parse::parse_digits, Answer, ParseError, Part};
+use itertools::Itertools;
+
+pub const IMPLEMENTED: &[Part] = &[Part::One, Part::Two];
+
+/// The alternating lengths of files and free space, in blocks.
+pub fn parse(input: &str) -> Result<Vec<u8>, ParseError> {
+    let disk_map = parse_digits(input, input.trim())?;
+    if disk_map.is_empty() {
+        return Err(ParseError::missing(input, "a disk map"));
+    }
+    Ok(disk_map)
+}
+
+pub fn solve(disk_map: Vec<u8>, part: Part) -> Result<Answer, ParseError> {
+    let mut memory = Vec::with_capacity(disk_map.len() * 5);
+    let mut is_file = true;
+    for (i, &n_blocks) in disk_map.iter().enumerate() {
+        // could optimize by minimizing the data size, but whatevs
+        let memory_content = if is_file {
+            let file_id = i / 2;
+            Some(file_id)
+        } else {
+            None
+        };
+        memory.extend(std::iter::repeat_n(memory_content, n_blocks as usize));
+        is_file ^= true;
+    }
+
+    fn checksum(memory: Vec<Option<usize>>) -> usize {
+        memory
+            .iter()
+            .enumerate()
+            .filter(|(_, data)| data.is_some())
+            .map(|(idx, data)| idx * data.unwrap())
+            .sum::<usize>()
+    }
+
+    match part {
+        Part::One => {
+            let mut cursor_left = 0;
+            // a disk map of only empty files leaves nothing to move
+            let mut cursor_right = memory.len().saturating_sub(1);
+            while cursor_left < cursor_right {
+                match (
+                    memory[cursor_left].is_none(),
+                    memory[cursor_right].is_some(),
+                ) {
+                    (true, true) => {
+                        memory.swap(cursor_left, cursor_right);
+                        cursor_left += 1;
+                        cursor_right -= 1;
+                    }
+                    (left_ready, right_ready) => {
+                        cursor_left += (!left_ready) as usize;
+                        cursor_right -= (!right_ready) as usize;
+                    }
+                };
+            }
+
+            Ok(checksum(memory).into())
+        }
+        Part::Two => {
+            #[derive(Clone, Copy)]
+            struct BlockChunk {
+                pos: usize,
+                len: usize,
+                file_id: Option<usize>,
+            }
+
+            let is_file = [true, false].into_iter().cycle();
+            let blocks = disk_map.iter().enumerate();
+            let mut chunks = blocks
+                .zip(is_file)
+                .map(|((pos, len), is_file)| BlockChunk {
+                    pos,
+                    len: *len as usize,
+                    file_id: is_file.then_some(pos / 2),
+                })
+                .collect_vec();
+
+            let mut memory = Vec::with_capacity(disk_map.len() * 5);
+            // let mut new_chunks = Vec::with_capacity(chunks.len());
+            for chunk_idx in 0..chunks.len() {
+                let mut chunk = chunks[chunk_idx];
+                if chunk.file_id.is_some() {
+                    memory.extend(std::iter::repeat_n(chunk.file_id, chunk.len));
+                } else {
+                    while chunk.len > 0 {
+                        // search file that fits
+                        if let Some((file_chunk_idx, &file_chunk)) = chunks
+                            .iter()
+                            .enumerate()
+                            .skip(chunk_idx + 1)
+                            .rev()
+                            .find(|(_, ch)| ch.file_id.is_some() && ch.len <= chunk.len)
+                        {
+                            memory.extend(std::iter::repeat_n(file_chunk.file_id, file_chunk.len));
+                            chunk.len -= file_chunk.len;
+                            chunk.pos += file_chunk.len;
+                            // This will result in neighboring empty chunks that are treated as separate entities, but
+                            // it doesn't matter for the purpose here.
+                            chunks[file_chunk_idx].file_id = None;
+                        } else {
+                            memory.extend(std::iter::repeat_n(None, chunk.len));
+                            break;
+                        }
+                    }
+                }
+            }
+
+            // for cell in &memory {
+            //     print!(
+            //         "{}",
+            //         cell.map_or('.', |num| char::from_digit(num as _, 10).unwrap())
+            //     );
+            // }
+            // println!("");
+            //println!("{:?}", memory);
+            Ok(checksum(memory).into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2333133121414131402\n";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::One),
+            Ok(Answer::from(1928))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            solve(parse(EXAMPLE).unwrap(), Part::Two),
+            Ok(Answer::from(2858))
+        );
+    }
+
+    /// The file id of each block, `None` for free blocks.
+    fn blocks(disk_map: &str) -> Vec<Option<usize>> {
+        let mut blocks = vec![];
+        for (i, digit) in disk_map.trim().bytes().enumerate() {
+            let content = (i % 2 == 0).then_some(i / 2);
+            blocks.extend(std::iter::repeat_n(content, (digit - b'0') as usize));
+        }
+        blocks
+    }
+
+    fn checksum(blocks: &[Option<usize>]) -> usize {
+        blocks
+            .iter()
+            .enumerate()
+            .map(|(pos, file_id)| pos * file_id.unwrap_or(0))
+            .sum()
+    }
+
+    /// Moves the last file block into the first free block until no gap is left.
+    fn compact_blocks_naive(disk_map: &str) -> usize {
+        let mut blocks = blocks(disk_map);
+        loop {
+            let first_free = blocks.iter().position(Option::is_none);
+            let last_used = blocks.iter().rposition(Option::is_some);
+            match (first_free, last_used) {
+                (Some(free), Some(used)) if free < used => blocks.swap(free, used),
+                _ => return checksum(&blocks),
+            }
+        }
+    }
+
+    /// Moves each file once, by decreasing id, into the leftmost span of free blocks it fits in.
+    fn compact_files_naive(disk_map: &str) -> usize {
+        let mut blocks = blocks(disk_map);
+        let max_file_id = blocks.iter().flatten().max().copied().unwrap_or(0);
+        for file_id in (0..=max_file_id).rev() {
+            let start = blocks.iter().position(|&b| b == Some(file_id)).unwrap();
+            let len = blocks[start..]
+                .iter()
+                .take_while(|&&b| b == Some(file_id))
+                .count();
+            let free_start = (0..start).find(|&pos| {
+                pos + len <= start && blocks[pos..pos + len].iter().all(Option::is_none)
+            });
+            if let Some(free_start) = free_start {
+                for offset in 0..len {
+                    blocks.swap(free_start + offset, start + offset);
+                }
+            }
+        }
+        checksum(&blocks)
+    }
+
+    #[test]
+    fn empty_disks() {
+        assert!(parse("").is_err());
+        assert!(parse("\n").is_err());
+        assert_eq!(solve(parse("0").unwrap(), Part::One), Ok(Answer::from(0)));
+        assert_eq!(
+            solve(parse("000\n").unwrap(), Part::Two),
+            Ok(Answer::from(0))
+        );
+    }
+
+    /// Files take 1 to 9 blocks, the free space in between 0 to 9.
+    fn disk_map() -> impl proptest::strategy::Strategy<Value = String> {
+        proptest::string::string_regex("[1-9]([0-9][1-9]){0,30}").unwrap()
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn two_cursor_compaction_matches_explicit_moves(disk_map in disk_map()) {
+            proptest::prop_assert_eq!(
+                solve(parse(&disk_map).unwrap(), Part::One),
+                Ok(Answer::from(compact_blocks_naive(&disk_map)))
+            );
+        }
+
+        #[test]
+        fn file_compaction_matches_explicit_moves(disk_map in disk_map()) {
+            proptest::prop_assert_eq!(
+                solve(parse(&disk_map).unwrap(), Part::Two),
+                Ok(Answer::from(compact_files_naive(&disk_map)))
+            );
+        }
+    }
+}
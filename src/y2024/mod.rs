@@ -0,0 +1,32 @@
+//! Solutions of the 2024 event.
+
+use crate::Solver;
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+// `new <day>` adds new days to this list and declares their modules above it.
+days! {
+    1 => day1,
+    2 => day2,
+    3 => day3,
+    4 => day4,
+    5 => day5,
+    6 => day6,
+    7 => day7,
+    8 => day8,
+    9 => day9,
+    10 => day10,
+    11 => day11,
+    12 => day12,
+}